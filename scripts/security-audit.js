@@ -209,7 +209,7 @@ async function runSecurityAudit() {
   
   const implementedFeatures = [
     { name: 'Overflow protection in claim_reward', check: 'u128' },
-    { name: 'Pool size limits', check: 'MAX_POOL_SIZE' },
+    { name: 'Pool size limits', check: 'validate_pool_addition' },
     { name: 'Security checks module', check: 'security_checks' },
     { name: 'Access control', check: 'access_control' },
     { name: 'Checked arithmetic', check: 'checked_' }
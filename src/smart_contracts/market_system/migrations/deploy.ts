@@ -23,7 +23,35 @@ module.exports = async function (provider: anchor.AnchorProvider) {
   );
   
   console.log(`Platform State PDA: ${platformState.toString()}`);
-  
+
+  // Accounts created by the first deployment are shorter than the current
+  // layouts and fail to load until they are migrated. The platform state
+  // goes first: migrate_market checks the authority against it.
+  const LEGACY_PLATFORM_STATE_SIZE = 8 + 137;
+  const LEGACY_MARKET_SIZE = 8 + 683;
+  const existing = await provider.connection.getAccountInfo(platformState);
+  if (existing && existing.data.length === LEGACY_PLATFORM_STATE_SIZE) {
+    console.log("📝 Migrating platform state to the current layout...");
+    await program.methods
+      .migratePlatformState()
+      .accounts({ platformState, authority: provider.wallet.publicKey })
+      .rpc({ commitment: 'confirmed' });
+  }
+  const legacyMarkets = await provider.connection.getProgramAccounts(program.programId, {
+    filters: [{ dataSize: LEGACY_MARKET_SIZE }],
+  });
+  for (const { pubkey } of legacyMarkets) {
+    console.log(`📝 Migrating market ${pubkey.toString()}...`);
+    const [marketEscrow] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market_escrow"), pubkey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .migrateMarket()
+      .accountsPartial({ market: pubkey, marketEscrow, platformState, authority: provider.wallet.publicKey })
+      .rpc({ commitment: 'confirmed' });
+  }
+
   // Check if already initialized
   try {
     const existingState = await program.account.platformState.fetch(platformState);
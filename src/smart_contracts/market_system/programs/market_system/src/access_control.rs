@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::ErrorCode;
+use crate::{security_checks, ErrorCode};
 
 #[account]
 #[derive(Default)]
@@ -13,6 +13,58 @@ impl AccessControl {
     pub const LEN: usize = 32 + 4 + (32 * Self::MAX_CREATORS); // admin + vec len + creators
 }
 
+/// Per-creator limits applied to whitelisted (non-admin) market creators.
+#[account]
+#[derive(Default)]
+pub struct CreatorConfig {
+    pub creator: Pubkey,
+    pub max_fee_rate: u64,                   // In basis points, capped by MAX_FEE_RATE
+    pub max_active_markets: u16,
+    pub active_markets: u16,
+    pub min_stake_tokens: u64,               // Creator stake floor, in whole tokens
+    pub allowed_categories: Vec<[u8; 20]>,   // Empty means any category
+}
+
+impl CreatorConfig {
+    pub const MAX_CATEGORIES: usize = 8;
+    pub const LEN: usize = 32 + // creator
+                           8 +  // max_fee_rate
+                           2 +  // max_active_markets
+                           2 +  // active_markets
+                           8 +  // min_stake_tokens
+                           4 + (20 * Self::MAX_CATEGORIES); // vec len + categories
+
+    pub fn allows_category(&self, category: &[u8; 20]) -> bool {
+        self.allowed_categories.is_empty() || self.allowed_categories.contains(category)
+    }
+
+    /// Creator stake floor in base units of a mint with `decimals`
+    pub fn min_stake_amount(&self, decimals: u8) -> Result<u64> {
+        security_checks::scale_to_mint_units(self.min_stake_tokens, decimals)
+    }
+
+    /// Checks a new market against this creator's limits and takes one of
+    /// their active market slots
+    pub fn open_market(&mut self, fee_rate: u64, stake: u64, category: &[u8; 20], decimals: u8) -> Result<()> {
+        require!(fee_rate <= self.max_fee_rate, ErrorCode::FeeTooHigh);
+        require!(stake >= self.min_stake_amount(decimals)?, ErrorCode::CreatorStakeTooLow);
+        require!(self.allows_category(category), ErrorCode::CategoryNotAllowed);
+        require!(
+            self.active_markets < self.max_active_markets,
+            ErrorCode::ActiveMarketLimitReached
+        );
+        self.active_markets = self.active_markets
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    /// Frees a slot once one of the creator's markets leaves the Active state
+    pub fn close_market(&mut self) {
+        self.active_markets = self.active_markets.saturating_sub(1);
+    }
+}
+
 pub fn initialize_access_control(ctx: Context<crate::InitializeAccessControl>) -> Result<()> {
     let access_control = &mut ctx.accounts.access_control;
     access_control.admin = ctx.accounts.admin.key();
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::security_checks;
+
+/// A mint the platform accepts as market collateral, with its own betting
+/// limits and fee settings. Markets may only be created on enabled mints.
+#[account]
+#[derive(Default)]
+pub struct CollateralConfig {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub enabled: bool,              // New markets may use this mint
+    pub min_bet_tokens: u64,        // Floor for bets and market minimums, in whole tokens
+    pub burn_enabled: bool,         // Apply stake, bet and claim burns
+    pub platform_fee_rate: u64,     // In basis points, caps the platform's bet fee
+    pub max_bet_tokens: u64,        // Per-transaction cap, in whole tokens
+    pub max_pool_tokens: u64,       // Per-market pool cap, in whole tokens
+    pub total_volume: u64,
+    pub total_burned: u64,
+    pub burn_sequence: u64,         // Sequence number of the last BurnEvent for this mint
+}
+
+impl CollateralConfig {
+    pub const LEN: usize = 32 + // mint
+                           1 +  // decimals
+                           1 +  // enabled
+                           8 +  // min_bet_tokens
+                           1 +  // burn_enabled
+                           8 +  // platform_fee_rate
+                           8 +  // max_bet_tokens
+                           8 +  // max_pool_tokens
+                           8 +  // total_volume
+                           8 +  // total_burned
+                           8;   // burn_sequence
+
+    /// Bet-time platform fee and burn rates after applying this mint's settings
+    pub fn bet_rates(&self, platform_fee_rate: u64, bet_burn_rate: u64) -> (u64, u64) {
+        (
+            platform_fee_rate.min(self.platform_fee_rate),
+            self.burn_rate(bet_burn_rate),
+        )
+    }
+
+    /// Bet and market minimum in base units of a mint with `decimals`
+    pub fn min_bet_amount(&self, decimals: u8) -> Result<u64> {
+        security_checks::scale_to_mint_units(self.min_bet_tokens, decimals)
+    }
+
+    /// Per-transaction bet cap in base units of a mint with `decimals`
+    pub fn max_bet_amount(&self, decimals: u8) -> Result<u64> {
+        security_checks::scale_to_mint_units(self.max_bet_tokens, decimals)
+    }
+
+    /// Per-market pool cap in base units of a mint with `decimals`
+    pub fn max_pool_amount(&self, decimals: u8) -> Result<u64> {
+        security_checks::scale_to_mint_units(self.max_pool_tokens, decimals)
+    }
+
+    pub fn burn_rate(&self, rate: u64) -> u64 {
+        if self.burn_enabled { rate } else { 0 }
+    }
+
+    pub fn record_activity(&mut self, volume: u64, burned: u64) -> Result<()> {
+        self.total_volume = security_checks::safe_add(self.total_volume, volume)?;
+        self.total_burned = security_checks::safe_add(self.total_burned, burned)?;
+        Ok(())
+    }
+
+    /// Advances and returns the burn sequence used to key this mint's BurnEvents
+    pub fn next_burn_sequence(&mut self) -> Result<u64> {
+        self.burn_sequence = security_checks::safe_add(self.burn_sequence, 1)?;
+        Ok(self.burn_sequence)
+    }
+}
@@ -0,0 +1,521 @@
+use anchor_lang::prelude::*;
+use crate::fees::{FeeSource, FeeSplit, FeeTier, PointsTier};
+use crate::parlay::ParlayLeg;
+use crate::{MarketStatus, MarketType};
+
+// Events emitted by every state transition so the backend can index
+// directly from the IDL instead of parsing log messages.
+
+#[event]
+pub struct PlatformInitialized {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub bet_burn_rate: u64,
+    pub claim_burn_rate: u64,
+    pub platform_fee_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PermissionlessCreationUpdated {
+    pub authority: Pubkey,
+    pub enabled: bool,
+    pub bond_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub authority: Pubkey,
+    pub previous_flags: u8,
+    pub pause_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnRelayerUpdated {
+    pub authority: Pubkey,
+    pub relayer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackCrankUpdated {
+    pub authority: Pubkey,
+    pub crank: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackPoolUpdated {
+    pub authority: Pubkey,
+    pub input_mint: Pubkey,
+    pub pool: Pubkey,
+    pub min_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackExecuted {
+    pub caller: Pubkey,
+    pub input_mint: Pubkey,
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub burn_amount: u64,
+    pub treasury_remaining: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformStatsSynced {
+    pub total_volume: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnAcknowledged {
+    pub receipt: Pubkey,
+    pub sequence: u64,
+    pub relayer: Pubkey,
+    pub burn_amount: u64,
+    pub proof_id: [u8; 64],
+    pub proof_id_len: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BurnReceiptClosed {
+    pub receipt: Pubkey,
+    pub sequence: u64,
+    pub payer: Pubkey,
+    pub relayer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralConfigUpdated {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub min_bet_tokens: u64,
+    pub burn_enabled: bool,
+    pub platform_fee_rate: u64,
+    pub max_bet_tokens: u64,
+    pub max_pool_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryVaultInitialized {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSplitsUpdated {
+    pub authority: Pubkey,
+    pub fee_splits: Vec<FeeSplit>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub authority: Pubkey,
+    pub fee_tiers: Vec<FeeTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsDiscountsUpdated {
+    pub authority: Pubkey,
+    pub pmp_mint: Pubkey,
+    pub points_tiers: Vec<PointsTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesAccrued {
+    pub market: Pubkey,
+    pub source: FeeSource,
+    pub fee: u64,
+    pub treasury: u64,
+    pub creator: u64,
+    pub referrer: u64,
+    pub stakers: u64,
+    pub sponsors: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakerFeesWithdrawn {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralBound {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccessControlInitialized {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketCreatorAdded {
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    pub creator_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketCreatorRemoved {
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    pub creator_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorConfigUpdated {
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    pub max_fee_rate: u64,
+    pub max_active_markets: u16,
+    pub active_markets: u16,
+    pub min_stake_tokens: u64,
+    pub allowed_categories: Vec<[u8; 20]>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub authority: Pubkey,
+    pub market_type: MarketType,
+    pub status: MarketStatus,
+    pub market_id: [u8; 32],
+    pub category: [u8; 20],
+    pub option_count: u8,
+    pub resolution_date: i64,
+    pub creator_fee_rate: u64,
+    pub min_bet_amount: u64,
+    pub token_mint: Pubkey,
+    pub stake_amount: u64,
+    pub burn_amount: u64,
+    pub creator_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketLimitsUpdated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub max_user_stake: u64,
+    pub max_option_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketSponsored {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub bonus_amount: u64,
+    pub seed_amounts: [u64; 4],
+    pub subsidy_bonus: u64,
+    pub subsidy_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorFeesClaimed {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorshipRefunded {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketApproved {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketRejected {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub bond_amount: u64,
+    pub bond_slashed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorBondReleased {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PredictionPlaced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub burn_amount: u64,
+    pub platform_fee: u64,
+    pub bet_burn_rate: u64,         // Rates applied after volume tier and points discounts
+    pub platform_fee_rate: u64,
+    pub points_discount_bps: u16,
+    pub transfer_fee: u64,          // Withheld by Token-2022 transfer-fee mints
+    pub position_amount: u64,
+    pub option_pool: u64,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapBetPlaced {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub bet_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub pool: Pubkey,
+    pub provider: Pubkey,
+    pub token_amount: u64,
+    pub usdc_amount: u64,
+    pub lp_amount: u64,
+    pub token_reserve: u64,
+    pub usdc_reserve: u64,
+    pub lp_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub pool: Pubkey,
+    pub provider: Pubkey,
+    pub token_amount: u64,
+    pub usdc_amount: u64,
+    pub lp_amount: u64,
+    pub token_reserve: u64,
+    pub usdc_reserve: u64,
+    pub lp_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensSwapped {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub is_token_to_usdc: bool,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub token_reserve: u64,
+    pub usdc_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub winning_option: u8,
+    pub winning_pool: u64,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketPaused {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketUnpaused {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub reward_amount: u64,
+    pub creator_fee: u64,
+    pub burn_amount: u64,
+    pub net_reward: u64,
+    pub winning_pool: u64,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PredictionRefunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub option_index: u8,
+    pub amount: u64,
+    pub total_refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowSurplusRecovered {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParlayHouseUpdated {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub edge_bps: u16,
+    pub max_multiplier_bps: u64,
+    pub max_leg_odds_bps: u64,
+    pub min_leg_pool_tokens: u64,
+    pub min_leg_bettors: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParlayLiquidityUpdated {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub balance: u64,
+    pub reserved: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParlayPlaced {
+    pub parlay: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub stake: u64,
+    pub payout: u64,
+    pub legs: Vec<ParlayLeg>,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParlaySettled {
+    pub parlay: Pubkey,
+    pub user: Pubkey,
+    pub won: bool,
+    pub voided_legs: u8,
+    pub paid_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParlayClosed {
+    pub parlay: Pubkey,
+    pub user: Pubkey,
+    pub paid_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformStateMigrated {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketMigrated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub total_claimed: u64,
+    pub total_refunded: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use crate::referral::ReferrerRewards;
+use crate::security_checks;
+use crate::{ErrorCode, Market};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeRecipient {
+    Treasury,
+    Creator,
+    Referrer,
+    Stakers,
+    Sponsors,
+}
+
+/// Where a fee was charged: the platform fee on bets or the creator fee on claims
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeSource {
+    Bet,
+    Claim,
+}
+
+/// One row of the platform fee-split table. Each column must sum to 10000.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeSplit {
+    pub recipient: FeeRecipient,
+    pub bet_bps: u16,    // Share of the platform fee charged at bet time
+    pub claim_bps: u16,  // Share of the creator fee charged at claim time
+}
+
+impl FeeSplit {
+    pub const LEN: usize = 1 + // recipient
+                           2 + // bet_bps
+                           2;  // claim_bps
+    pub const MAX_SPLITS: usize = 5; // One row per recipient kind
+
+    /// Bet fees to the treasury, claim fees to the creator
+    pub fn defaults() -> Vec<FeeSplit> {
+        vec![
+            FeeSplit { recipient: FeeRecipient::Treasury, bet_bps: 10000, claim_bps: 0 },
+            FeeSplit { recipient: FeeRecipient::Creator, bet_bps: 0, claim_bps: 10000 },
+        ]
+    }
+
+    pub fn validate(splits: &[FeeSplit]) -> Result<()> {
+        require!(
+            !splits.is_empty() && splits.len() <= Self::MAX_SPLITS,
+            ErrorCode::InvalidFeeSplit
+        );
+        let mut bet_total: u32 = 0;
+        let mut claim_total: u32 = 0;
+        for (i, split) in splits.iter().enumerate() {
+            require!(
+                !splits[..i].iter().any(|other| other.recipient == split.recipient),
+                ErrorCode::InvalidFeeSplit
+            );
+            bet_total += split.bet_bps as u32;
+            claim_total += split.claim_bps as u32;
+        }
+        require!(bet_total == 10000 && claim_total == 10000, ErrorCode::InvalidFeeSplit);
+        Ok(())
+    }
+
+    pub fn weight(&self, source: FeeSource) -> u64 {
+        match source {
+            FeeSource::Bet => self.bet_bps as u64,
+            FeeSource::Claim => self.claim_bps as u64,
+        }
+    }
+}
+
+/// Reduced bet-time rates for users whose cumulative volume in the bet's
+/// collateral reached `min_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeTier {
+    pub min_volume: u64,        // In whole tokens, so one table fits every mint
+    pub platform_fee_rate: u64, // In basis points (1% = 100)
+    pub bet_burn_rate: u64,     // In basis points (1% = 100)
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + // min_volume
+                           8 + // platform_fee_rate
+                           8;  // bet_burn_rate
+    pub const MAX_TIERS: usize = 5;
+
+    /// Tiers must have rising thresholds and may only lower the base rates
+    pub fn validate(tiers: &[FeeTier], platform_fee_rate: u64, bet_burn_rate: u64) -> Result<()> {
+        require!(tiers.len() <= Self::MAX_TIERS, ErrorCode::InvalidFeeTier);
+        let mut previous: Option<&FeeTier> = None;
+        for tier in tiers {
+            let (fee_cap, burn_cap) = previous
+                .map(|p| (p.platform_fee_rate, p.bet_burn_rate))
+                .unwrap_or((platform_fee_rate, bet_burn_rate));
+            require!(
+                tier.platform_fee_rate <= fee_cap && tier.bet_burn_rate <= burn_cap,
+                ErrorCode::InvalidFeeTier
+            );
+            if let Some(previous) = previous {
+                require!(tier.min_volume > previous.min_volume, ErrorCode::InvalidFeeTier);
+            }
+            previous = Some(tier);
+        }
+        Ok(())
+    }
+}
+
+/// Discount off the platform fee for users holding at least `min_balance`
+/// PRIMAPE points (PMP) in their Token-2022 points account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PointsTier {
+    pub min_balance: u64,
+    pub discount_bps: u16,      // Share of the platform fee waived (10000 = all)
+}
+
+impl PointsTier {
+    pub const LEN: usize = 8 + // min_balance
+                           2;  // discount_bps
+    pub const MAX_TIERS: usize = 5;
+
+    /// Tiers must have rising thresholds and non-decreasing discounts
+    pub fn validate(tiers: &[PointsTier]) -> Result<()> {
+        require!(tiers.len() <= Self::MAX_TIERS, ErrorCode::InvalidPointsTier);
+        let mut previous: Option<&PointsTier> = None;
+        for tier in tiers {
+            require!(tier.discount_bps <= 10000, ErrorCode::InvalidPointsTier);
+            if let Some(previous) = previous {
+                require!(
+                    tier.min_balance > previous.min_balance
+                        && tier.discount_bps >= previous.discount_bps,
+                    ErrorCode::InvalidPointsTier
+                );
+            }
+            previous = Some(tier);
+        }
+        Ok(())
+    }
+
+    /// `rate` with `discount_bps` of it waived, rounded down
+    pub fn discounted_rate(rate: u64, discount_bps: u16) -> Result<u64> {
+        require!(discount_bps <= 10000, ErrorCode::InvalidPointsTier);
+        security_checks::safe_div(
+            security_checks::safe_mul(rate, 10000 - discount_bps as u64)?,
+            10000
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeShares {
+    pub treasury: u64,
+    pub creator: u64,
+    pub referrer: u64,
+    pub stakers: u64,
+    pub sponsors: u64,
+}
+
+impl FeeShares {
+    /// Splits `fee` by the table; rounding dust goes to the treasury
+    pub fn split(fee: u64, splits: &[FeeSplit], source: FeeSource) -> Result<FeeShares> {
+        let mut shares = FeeShares::default();
+        let mut distributed: u64 = 0;
+        for split in splits {
+            let share = security_checks::safe_div(
+                security_checks::safe_mul(fee, split.weight(source))?,
+                10000
+            )?;
+            distributed = security_checks::safe_add(distributed, share)?;
+            match split.recipient {
+                FeeRecipient::Treasury => shares.treasury = share,
+                FeeRecipient::Creator => shares.creator = share,
+                FeeRecipient::Referrer => shares.referrer = share,
+                FeeRecipient::Stakers => shares.stakers = share,
+                FeeRecipient::Sponsors => shares.sponsors = share,
+            }
+        }
+        shares.treasury = security_checks::safe_add(
+            shares.treasury,
+            security_checks::safe_sub(fee, distributed)?
+        )?;
+        Ok(shares)
+    }
+}
+
+/// Per-mint accounting for the fees held in the treasury vault. Creator
+/// shares are tracked per market in `Market::creator_fees_accrued`, sponsor
+/// shares per market through `Market::sponsor_fee_per_share`.
+#[account]
+#[derive(Default)]
+pub struct FeeLedger {
+    pub mint: Pubkey,
+    pub treasury_accrued: u64,  // Withdrawable by the platform treasury
+    pub stakers_accrued: u64,   // Withdrawable for staker rewards
+    pub creators_accrued: u64,  // Owed to market creators across all markets
+    pub referrers_accrued: u64, // Owed to referrers across all ReferrerRewards
+    pub sponsors_accrued: u64,  // Owed to market sponsors across all Sponsorships
+}
+
+impl FeeLedger {
+    pub const LEN: usize = 32 + // mint
+                           8 +  // treasury_accrued
+                           8 +  // stakers_accrued
+                           8 +  // creators_accrued
+                           8 +  // referrers_accrued
+                           8;   // sponsors_accrued
+
+    /// Books a fee that has been moved into the treasury vault. The referrer
+    /// and sponsor shares go to the treasury when the bet has no referrer or
+    /// the market no sponsors.
+    pub fn accrue(
+        &mut self,
+        market: &mut Market,
+        referrer_rewards: Option<&mut ReferrerRewards>,
+        shares: &FeeShares,
+    ) -> Result<()> {
+        match referrer_rewards {
+            Some(rewards) => {
+                rewards.accrued = security_checks::safe_add(rewards.accrued, shares.referrer)?;
+                rewards.total_earned = security_checks::safe_add(rewards.total_earned, shares.referrer)?;
+                self.referrers_accrued = security_checks::safe_add(self.referrers_accrued, shares.referrer)?;
+                self.treasury_accrued = security_checks::safe_add(self.treasury_accrued, shares.treasury)?;
+            }
+            None => {
+                self.treasury_accrued = security_checks::safe_add(
+                    self.treasury_accrued,
+                    security_checks::safe_add(shares.treasury, shares.referrer)?
+                )?;
+            }
+        }
+        if market.subsidy_total > 0 {
+            market.accrue_sponsor_fees(shares.sponsors)?;
+            self.sponsors_accrued = security_checks::safe_add(self.sponsors_accrued, shares.sponsors)?;
+        } else {
+            self.treasury_accrued = security_checks::safe_add(self.treasury_accrued, shares.sponsors)?;
+        }
+        self.stakers_accrued = security_checks::safe_add(self.stakers_accrued, shares.stakers)?;
+        self.creators_accrued = security_checks::safe_add(self.creators_accrued, shares.creator)?;
+        market.creator_fees_accrued = security_checks::safe_add(market.creator_fees_accrued, shares.creator)?;
+        Ok(())
+    }
+}
@@ -1,29 +1,46 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 pub mod market;
 pub mod token;
 pub mod access_control;
 pub mod security_checks;
-// pub mod utils;
+pub mod events;
+pub mod fees;
+pub mod referral;
+pub mod collateral;
+pub mod sponsorship;
+pub mod parlay;
+pub mod migration;
+pub mod utils;
 // pub mod security_fixes;
 // pub mod security_fixes_implementation;
 
-use crate::market::market_system::*;
-use crate::access_control::AccessControl;
-use crate::security_checks::*;
+use crate::access_control::{AccessControl, CreatorConfig};
+use crate::events::*;
+use crate::fees::{FeeLedger, FeeShares, FeeSource, FeeSplit, FeeTier, PointsTier};
+use crate::referral::{Referral, ReferrerRewards};
+use crate::collateral::CollateralConfig;
+use crate::sponsorship::Sponsorship;
+use crate::parlay::{LegResult, Parlay, ParlayHouse, ParlayLeg};
+use crate::migration::{LegacyMarket, LegacyPlatformState};
+use crate::utils::believeapp_integration::{self, BurnType};
+use crate::utils::meteora_integration as meteora;
+// The #[program] macro resolves instruction account structs from the crate root
+pub use crate::utils::meteora_integration::*;
 
 declare_id!("APESCaeLW5RuxNnpNARtDZnSgeVFC5f37Z3VFNKupJUS");
 
-// Global constants for safety
-const MAX_POOL_SIZE: u64 = 1_000_000_000 * 1_000_000; // 1B with 6 decimals
-
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1, // discriminator + authority + token_mint + treasury + rates + totals + is_paused
+        space = 8 + PlatformState::LEN,
         seeds = [b"platform_state"],
         bump
     )]
@@ -35,160 +52,277 @@ pub struct Initialize<'info> {
     /// CHECK: Treasury account
     pub treasury: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeAccessControl<'info> {
+pub struct UpdatePlatform<'info> {
     #[account(
-        init,
-        payer = admin,
-        space = 8 + AccessControl::LEN,
-        seeds = [b"access_control"],
-        bump
+        mut,
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
     )]
-    pub access_control: Account<'info, AccessControl>,
-    #[account(mut)]
-    pub admin: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
 }
 
+/// Bets, claims and creation only write their mint's CollateralConfig, so
+/// the platform totals are copied over from the platform token's config
 #[derive(Accounts)]
-pub struct AddMarketCreator<'info> {
+pub struct SyncPlatformStats<'info> {
     #[account(
         mut,
-        seeds = [b"access_control"],
+        seeds = [b"platform_state"],
         bump,
-        has_one = admin @ ErrorCode::Unauthorized
     )]
-    pub access_control: Account<'info, AccessControl>,
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        seeds = [b"collateral", platform_state.token_mint.as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
 }
 
 #[derive(Accounts)]
-pub struct RemoveMarketCreator<'info> {
+pub struct SetCollateralConfig<'info> {
     #[account(
-        mut,
-        seeds = [b"access_control"],
+        seeds = [b"platform_state"],
         bump,
-        has_one = admin @ ErrorCode::Unauthorized
+        has_one = authority @ ErrorCode::Unauthorized
     )]
-    pub access_control: Account<'info, AccessControl>,
+    pub platform_state: Account<'info, PlatformState>,
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CollateralConfig::LEN,
+        seeds = [b"collateral", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreateMarket<'info> {
+pub struct AcknowledgeBurn<'info> {
     #[account(
-        init,
-        payer = creator,
-        space = 8 + Market::LEN
+        seeds = [b"platform_state"],
+        bump,
     )]
-    pub market: Account<'info, Market>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        constraint = !burn_receipt.acknowledged @ ErrorCode::BurnAlreadyAcknowledged
+    )]
+    pub burn_receipt: Account<'info, BurnReceipt>,
+    #[account(
+        constraint = relayer.key() == platform_state.burn_relayer @ ErrorCode::Unauthorized
+    )]
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBurnReceipt<'info> {
     #[account(
         seeds = [b"platform_state"],
         bump,
     )]
     pub platform_state: Account<'info, PlatformState>,
-    #[account(mut)]
-    pub creator: Signer<'info>,
     #[account(
         mut,
-        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized,
-        constraint = creator_token_account.mint == platform_state.token_mint @ ErrorCode::InvalidMint
+        close = payer,
+        constraint = burn_receipt.acknowledged @ ErrorCode::BurnNotAcknowledged
+    )]
+    pub burn_receipt: Account<'info, BurnReceipt>,
+    /// The burner, who paid the receipt's rent
+    /// CHECK: Only receives lamports
+    #[account(mut, address = burn_receipt.user @ ErrorCode::Unauthorized)]
+    pub payer: AccountInfo<'info>,
+    #[account(
+        constraint = relayer.key() == platform_state.burn_relayer @ ErrorCode::Unauthorized
+    )]
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
     #[account(
         init,
-        payer = creator,
-        seeds = [b"market_escrow", market.key().as_ref()],
+        payer = authority,
+        seeds = [b"treasury_vault", token_mint.key().as_ref()],
         bump,
         token::mint = token_mint,
-        token::authority = market_escrow,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
     )]
-    pub market_escrow: Account<'info, TokenAccount>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
-        mut,
-        constraint = burn_token_account.mint == platform_state.token_mint @ ErrorCode::InvalidMint
+        init,
+        payer = authority,
+        space = 8 + FeeLedger::LEN,
+        seeds = [b"fee_ledger", token_mint.key().as_ref()],
+        bump,
     )]
-    pub burn_token_account: Account<'info, TokenAccount>,
-    /// CHECK: Token mint for the market
-    pub token_mint: AccountInfo<'info>,
-    #[account(seeds = [b"access_control"], bump)]
-    pub access_control: Account<'info, AccessControl>,
+    pub fee_ledger: Account<'info, FeeLedger>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(option_index: u8)]
-pub struct PlacePrediction<'info> {
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
     #[account(
         mut,
-        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+        seeds = [b"treasury_vault", treasury_vault.mint.as_ref()],
+        bump,
     )]
-    pub market: Account<'info, Market>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", treasury_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == platform_state.treasury @ ErrorCode::Unauthorized,
+        constraint = treasury_token_account.mint == treasury_vault.mint @ ErrorCode::InvalidMint
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = treasury_vault.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Spends accrued treasury fees of another mint on the platform token through
+/// Meteora and burns what was bought
+#[derive(Accounts)]
+#[instruction(amount_in: u64, minimum_amount_out: u64, burn_nonce: u64)]
+pub struct BuybackAndBurn<'info> {
     #[account(
         seeds = [b"platform_state"],
         bump,
+        constraint = caller.key() == platform_state.authority
+            || caller.key() == platform_state.buyback_crank @ ErrorCode::Unauthorized
     )]
     pub platform_state: Account<'info, PlatformState>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub caller: Signer<'info>,
+    #[account(
+        mint::token_program = input_token_program,
+        constraint = input_mint.key() != platform_state.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub input_mint: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
-        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        seeds = [b"treasury_vault", input_mint.key().as_ref()],
+        bump,
+        token::token_program = input_token_program,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub input_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
-        seeds = [b"market_escrow", market.key().as_ref()],
+        seeds = [b"fee_ledger", input_mint.key().as_ref()],
         bump,
     )]
-    pub market_escrow: Account<'info, TokenAccount>,
+    pub input_fee_ledger: Account<'info, FeeLedger>,
     #[account(
         mut,
-        constraint = burn_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        address = platform_state.token_mint @ ErrorCode::InvalidMint
     )]
-    pub burn_token_account: Account<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// Receives the bought platform tokens, which are burned straight away
     #[account(
         mut,
-        constraint = treasury_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        seeds = [b"treasury_vault", token_mint.key().as_ref()],
+        bump,
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// Holds the platform token's burn totals and burn sequence
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + Prediction::LEN,
-        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        mut,
+        seeds = [b"collateral", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    pub meteora: MeteoraSwap<'info>,
+    /// Required whenever anything is burned
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + BurnReceipt::LEN,
+        seeds = [
+            b"burn_receipt",
+            Pubkey::default().as_ref(),
+            caller.key().as_ref(),
+            burn_nonce.to_le_bytes().as_ref(),
+        ],
         bump
     )]
-    pub prediction: Account<'info, Prediction>,
+    pub burn_receipt: Option<Account<'info, BurnReceipt>>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+    /// Owns the platform token, which is burned
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Owns the input mint, which is swapped; may differ from token_program
+    pub input_token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveMarket<'info> {
+pub struct WithdrawStakerFees<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
     #[account(
         mut,
-        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+        seeds = [b"treasury_vault", treasury_vault.mint.as_ref()],
+        bump,
     )]
-    pub market: Account<'info, Market>,
-    pub resolver: Signer<'info>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", treasury_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(
+        mut,
+        constraint = staker_rewards_account.mint == treasury_vault.mint @ ErrorCode::InvalidMint
+    )]
+    pub staker_rewards_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = treasury_vault.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-#[instruction(option_index: u8)]
-pub struct ClaimReward<'info> {
+pub struct ClaimCreatorFees<'info> {
     #[account(
-        constraint = market.status == MarketStatus::Resolved @ ErrorCode::MarketNotResolved
+        mut,
+        constraint = creator.key() == market.creator @ ErrorCode::Unauthorized
     )]
     pub market: Account<'info, Market>,
     #[account(
@@ -196,96 +330,1046 @@ pub struct ClaimReward<'info> {
         bump,
     )]
     pub platform_state: Account<'info, PlatformState>,
+    pub creator: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        constraint = creator_token_account.owner == market.creator @ ErrorCode::Unauthorized,
+        constraint = creator_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.token_mint.as_ref()],
         bump,
-        constraint = prediction.user == user.key() @ ErrorCode::Unauthorized,
-        constraint = !prediction.claimed @ ErrorCode::AlreadyClaimed
     )]
-    pub prediction: Account<'info, Prediction>,
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
-        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        seeds = [b"fee_ledger", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerRewards::LEN,
+        seeds = [b"referrer_rewards", referrer.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub platform_state: Account<'info, PlatformState>,
+    pub referrer: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"market_escrow", market.key().as_ref()],
+        seeds = [b"referrer_rewards", referrer.key().as_ref(), referrer_rewards.mint.as_ref()],
         bump,
+        has_one = referrer @ ErrorCode::Unauthorized
     )]
-    pub market_escrow: Account<'info, TokenAccount>,
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
     #[account(
         mut,
-        constraint = creator_token_account.owner == market.creator @ ErrorCode::Unauthorized,
-        constraint = creator_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        constraint = referrer_token_account.owner == referrer.key() @ ErrorCode::Unauthorized,
+        constraint = referrer_token_account.mint == referrer_rewards.mint @ ErrorCode::InvalidMint
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
-        constraint = burn_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        seeds = [b"treasury_vault", referrer_rewards.mint.as_ref()],
+        bump,
     )]
-    pub burn_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-}
-
-#[derive(Accounts)]
-pub struct CancelMarket<'info> {
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
-        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive,
-        constraint = authority.key() == market.authority @ ErrorCode::Unauthorized
+        seeds = [b"fee_ledger", referrer_rewards.mint.as_ref()],
+        bump,
     )]
-    pub market: Account<'info, Market>,
-    pub authority: Signer<'info>,
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(address = treasury_vault.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-#[instruction(option_index: u8)]
-pub struct EmergencyWithdraw<'info> {
+pub struct InitializeAccessControl<'info> {
     #[account(
-        constraint = market.status == MarketStatus::Cancelled @ ErrorCode::MarketNotCancelled
+        init,
+        payer = admin,
+        space = 8 + AccessControl::LEN,
+        seeds = [b"access_control"],
+        bump
     )]
-    pub market: Account<'info, Market>,
+    pub access_control: Account<'info, AccessControl>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddMarketCreator<'info> {
     #[account(
         mut,
-        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        seeds = [b"access_control"],
         bump,
-        constraint = prediction.user == user.key() @ ErrorCode::Unauthorized,
-        constraint = !prediction.claimed @ ErrorCode::AlreadyClaimed
+        has_one = admin @ ErrorCode::Unauthorized
     )]
-    pub prediction: Account<'info, Prediction>,
+    pub access_control: Account<'info, AccessControl>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMarketCreator<'info> {
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
-        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+        seeds = [b"access_control"],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub access_control: Account<'info, AccessControl>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct SetCreatorConfig<'info> {
     #[account(
-        mut,
-        seeds = [b"market_escrow", market.key().as_ref()],
+        seeds = [b"access_control"],
         bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CreatorConfig::LEN,
+        seeds = [b"creator_config", creator.as_ref()],
+        bump
     )]
-    pub market_escrow: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub creator_config: Account<'info, CreatorConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-#[account]
-pub struct Market {
-    pub authority: Pubkey,
-    pub creator: Pubkey,
-    pub market_type: MarketType,
-    pub question: [u8; 200],        // Fixed 200 bytes for question
-    pub question_len: u16,          // Actual length of question
-    pub option_1: [u8; 50],         // Fixed size for option 1
-    pub option_2: [u8; 50],         // Fixed size for option 2
-    pub option_3: [u8; 50],         // Fixed size for option 3 (if needed)
-    pub option_4: [u8; 50],         // Fixed size for option 4 (if needed)
+#[derive(Accounts)]
+pub struct CreateMarket<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Market::LEN
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized,
+        constraint = creator_token_account.mint == token_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = market_escrow,
+        token::token_program = token_program,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// The market's collateral; must be whitelisted and enabled
+    #[account(
+        mut,
+        seeds = [b"collateral", token_mint.key().as_ref()],
+        bump,
+        constraint = collateral_config.enabled @ ErrorCode::CollateralNotEnabled
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    #[account(seeds = [b"access_control"], bump)]
+    pub access_control: Account<'info, AccessControl>,
+    /// Required for every creator except the access control admin
+    #[account(
+        mut,
+        seeds = [b"creator_config", creator.key().as_ref()],
+        bump,
+    )]
+    pub creator_config: Option<Account<'info, CreatorConfig>>,
+    /// Required whenever the stake burn is non-zero. The market is new, so
+    /// its creation burn always uses nonce 0.
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BurnReceipt::LEN,
+        seeds = [
+            b"burn_receipt",
+            market.key().as_ref(),
+            creator.key().as_ref(),
+            0u64.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub burn_receipt: Option<Account<'info, BurnReceipt>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_index: u8, amount: u64, burn_nonce: u64)]
+pub struct PlacePrediction<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Prediction::LEN,
+        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        bump
+    )]
+    pub prediction: Account<'info, Prediction>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::LEN,
+        seeds = [b"user_stats", user.key().as_ref(), market.token_mint.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    /// User's PMP points account; its balance selects a points discount tier
+    #[account(
+        constraint = pmp_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = pmp_token_account.mint == platform_state.pmp_mint @ ErrorCode::InvalidMint
+    )]
+    pub pmp_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The user's referral in this mint, checked on every bet
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Referral::LEN,
+        seeds = [b"referral", user.key().as_ref(), market.token_mint.as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+    /// Binds the user to its referrer on their first bet; required on every
+    /// bet once a referrer is bound
+    #[account(
+        mut,
+        constraint = referrer_rewards.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+    /// Required whenever the bet burn is non-zero
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BurnReceipt::LEN,
+        seeds = [
+            b"burn_receipt",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            burn_nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub burn_receipt: Option<Account<'info, BurnReceipt>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Swaps another mint into the market's collateral through the integrated
+/// liquidity pool, then places the bet with the swap output
+#[derive(Accounts)]
+#[instruction(option_index: u8)]
+pub struct PlacePredictionWithSwap<'info> {
+    pub bet: PlacePrediction<'info>,
+    #[account(
+        mut,
+        constraint = pool_state.is_active @ ErrorCode::PoolInactive
+    )]
+    pub pool_state: Account<'info, LiquidityPoolState>,
+    #[account(mint::token_program = input_token_program)]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = user_input_account.owner == bet.user.key() @ ErrorCode::Unauthorized,
+        constraint = user_input_account.mint == input_mint.key() @ ErrorCode::InvalidMint,
+        token::token_program = input_token_program,
+    )]
+    pub user_input_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"pool_token_account", pool_state.key().as_ref()],
+        bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"pool_usdc_account", pool_state.key().as_ref()],
+        bump,
+    )]
+    pub pool_usdc_account: InterfaceAccount<'info, TokenAccount>,
+    /// Owns the input mint, which may differ from the collateral's program
+    pub input_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        mut,
+        constraint = market.is_live() @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub resolver: Signer<'info>,
+    /// Required when the market counts toward its creator's active market quota
+    #[account(
+        mut,
+        seeds = [b"creator_config", market.creator.as_ref()],
+        bump,
+    )]
+    pub creator_config: Option<Account<'info, CreatorConfig>>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_index: u8, burn_nonce: u64)]
+pub struct ClaimReward<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Resolved @ ErrorCode::MarketNotResolved
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        bump,
+        constraint = prediction.user == user.key() @ ErrorCode::Unauthorized,
+        constraint = !prediction.claimed @ ErrorCode::AlreadyClaimed
+    )]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(
+        mut,
+        address = market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    /// Required whenever the claim burn is non-zero
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BurnReceipt::LEN,
+        seeds = [
+            b"burn_receipt",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            burn_nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub burn_receipt: Option<Account<'info, BurnReceipt>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(
+        mut,
+        constraint = market.is_live() @ ErrorCode::MarketNotActive,
+        constraint = authority.key() == market.authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    /// Required when the market counts toward its creator's active market quota
+    #[account(
+        mut,
+        seeds = [b"creator_config", market.creator.as_ref()],
+        bump,
+    )]
+    pub creator_config: Option<Account<'info, CreatorConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketPause<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == market.authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketLimits<'info> {
+    #[account(
+        mut,
+        constraint = market.is_live() @ ErrorCode::MarketNotActive,
+        constraint = authority.key() == market.authority
+            || authority.key() == market.creator @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SponsorMarket<'info> {
+    #[account(
+        mut,
+        constraint = market.is_live()
+            || market.status == MarketStatus::PendingReview @ ErrorCode::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::Unauthorized,
+        constraint = sponsor_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + Sponsorship::LEN,
+        seeds = [b"sponsorship", market.key().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    #[account(
+        seeds = [b"collateral", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSponsorFees<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"sponsorship", market.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::Unauthorized,
+        constraint = sponsor_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSponsorship<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"sponsorship", market.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+        constraint = !sponsorship.refunded @ ErrorCode::AlreadyClaimed
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::Unauthorized,
+        constraint = sponsor_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMarket<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::PendingReview @ ErrorCode::MarketNotPendingReview,
+        constraint = authority.key() == market.authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectMarket<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::PendingReview @ ErrorCode::MarketNotPendingReview,
+        constraint = authority.key() == market.authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == market.creator @ ErrorCode::Unauthorized,
+        constraint = creator_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", market.token_mint.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseCreatorBond<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Resolved
+            || market.status == MarketStatus::Cancelled @ ErrorCode::MarketNotFinalized,
+        constraint = creator.key() == market.creator @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key() @ ErrorCode::Unauthorized,
+        constraint = creator_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct EscrowReconciliation<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverEscrowSurplus<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The market escrow itself, or any other token account owned by the escrow PDA
+    #[account(
+        mut,
+        constraint = source_token_account.owner == market_escrow.key() @ ErrorCode::Unauthorized
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Recovered tokens go to the treasury vault of their mint
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Credited with what the treasury vault receives, so it can be withdrawn
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(address = source_token_account.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_index: u8)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Cancelled @ ErrorCode::MarketNotCancelled
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"prediction", market.key().as_ref(), user.key().as_ref(), &[option_index]],
+        bump,
+        constraint = prediction.user == user.key() @ ErrorCode::Unauthorized,
+        constraint = !prediction.claimed @ ErrorCode::AlreadyClaimed
+    )]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == market.token_mint @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = market.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeParlayHouse<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Parlays are only offered on whitelisted collateral
+    #[account(
+        seeds = [b"collateral", token_mint.key().as_ref()],
+        bump,
+        constraint = collateral_config.enabled @ ErrorCode::CollateralNotEnabled
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ParlayHouse::LEN,
+        seeds = [b"parlay_house", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"parlay_vault", token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = parlay_vault,
+        token::token_program = token_program,
+    )]
+    pub parlay_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateParlayHouse<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"parlay_house", parlay_house.mint.as_ref()],
+        bump,
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+}
+
+#[derive(Accounts)]
+pub struct ManageParlayLiquidity<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"parlay_house", parlay_house.mint.as_ref()],
+        bump,
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", parlay_house.mint.as_ref()],
+        bump,
+    )]
+    pub parlay_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+        constraint = authority_token_account.mint == parlay_house.mint @ ErrorCode::InvalidMint
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = parlay_house.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Leg markets are passed as remaining accounts, in leg order
+#[derive(Accounts)]
+#[instruction(parlay_id: u64)]
+pub struct PlaceParlay<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == token_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"parlay_house", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub parlay_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Parlay::LEN,
+        seeds = [b"parlay", user.key().as_ref(), parlay_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// Supplies the claim burn rate taken off leg odds
+    #[account(
+        seeds = [b"collateral", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless; leg markets are passed as remaining accounts, in leg order
+#[derive(Accounts)]
+pub struct SettleParlay<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    #[account(
+        mut,
+        seeds = [b"parlay", parlay.user.as_ref(), parlay.parlay_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = !parlay.settled @ ErrorCode::AlreadyClaimed
+    )]
+    pub parlay: Account<'info, Parlay>,
+    #[account(
+        mut,
+        seeds = [b"parlay_house", parlay.mint.as_ref()],
+        bump,
+    )]
+    pub parlay_house: Account<'info, ParlayHouse>,
+    #[account(
+        mut,
+        seeds = [b"parlay_vault", parlay.mint.as_ref()],
+        bump,
+    )]
+    pub parlay_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = user_token_account.owner == parlay.user @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == parlay.mint @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = parlay.mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseParlay<'info> {
+    #[account(
+        mut,
+        seeds = [b"parlay", user.key().as_ref(), parlay.parlay_id.to_le_bytes().as_ref()],
+        bump,
+        close = user,
+        constraint = parlay.settled @ ErrorCode::ParlayNotSettled
+    )]
+    pub parlay: Account<'info, Parlay>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Platform state still in the first deployment's layout, which can't be
+/// loaded as PlatformState until it is migrated
+#[derive(Accounts)]
+pub struct MigratePlatformState<'info> {
+    /// CHECK: Decoded as LegacyPlatformState; the authority is checked there
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAccountLayout
+    )]
+    pub platform_state: UncheckedAccount<'info>,
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// A market still in the first deployment's layout. Needs the platform
+/// state migrated first.
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    /// CHECK: Decoded as LegacyMarket after checking its discriminator
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidAccountLayout)]
+    pub market: UncheckedAccount<'info>,
+    /// What it still holds is what resolved or cancelled markets haven't paid out
+    #[account(
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub market_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct Market {
+    pub authority: Pubkey,
+    pub creator: Pubkey,
+    pub market_type: MarketType,
+    pub question: [u8; 200],        // Fixed 200 bytes for question
+    pub question_len: u16,          // Actual length of question
+    pub option_1: [u8; 50],         // Fixed size for option 1
+    pub option_2: [u8; 50],         // Fixed size for option 2
+    pub option_3: [u8; 50],         // Fixed size for option 3 (if needed)
+    pub option_4: [u8; 50],         // Fixed size for option 4 (if needed)
     pub option_count: u8,           // Number of options (2-4)
     pub resolution_date: i64,
     pub creator_fee_rate: u64,
@@ -300,33 +1384,214 @@ pub struct Market {
     pub total_pool: u64,
     pub market_id: [u8; 32],        // Fixed size ID
     pub category: [u8; 20],         // Fixed size category
+    pub tracks_creator_quota: bool, // Counts toward the creator's active market quota
+    pub creator_bond: u64,          // Refundable bond held in escrow (permissionless markets)
+    pub total_claimed: u64,         // Gross rewards paid out of escrow (incl. fees and burns)
+    pub total_refunded: u64,        // Stakes returned by emergency_withdraw
+    pub volume: u64,                // Gross amount bet, before burns and fees
+    pub bettor_count: u32,          // Predictions opened (one per user and option)
+    pub fees_collected: u64,        // Platform fees at bet time plus creator fees at claim
+    pub creator_fees_accrued: u64,  // Creator's unclaimed share held in the treasury vault
+    pub max_user_stake: u64,        // Cap on one user's position per option (0 = uncapped)
+    pub max_option_share_bps: u16,  // Cap on one position's share of its option pool (0 = uncapped)
+    pub subsidy_bonus: u64,         // Sponsor bonus paid to winners on top of total_pool
+    pub subsidy_seed: [u64; 4],     // Sponsor seed included in each option pool
+    pub subsidy_total: u64,         // All sponsor deposits; weights sponsor fee shares
+    pub subsidy_refunded: u64,      // Subsidy returned to sponsors
+    pub sponsor_fee_per_share: u128, // Sponsor fees per subsidy token, scaled by Sponsorship::FEE_SCALE
+    pub creator_stake: u64,         // Whitelisted creator's stake held in escrow until finalization
+    pub pools_slot: u64,            // Slot of the last change to the option pools
+    pub settled_pools: [u64; 4],    // Option pools as they stood before pools_slot
+}
+
+impl Market {
+    // Fixed size allocation
+    pub const LEN: usize = 32 + // authority
+                           32 + // creator
+                           33 + // market_type (enum with padding)
+                           200 + // question
+                           2 +  // question_len
+                           50 + // option_1
+                           50 + // option_2
+                           50 + // option_3
+                           50 + // option_4
+                           1 +  // option_count
+                           8 +  // resolution_date
+                           8 +  // creator_fee_rate
+                           8 +  // min_bet_amount
+                           32 + // token_mint
+                           33 + // status (enum with padding)
+                           2 +  // winning_option (Option<u8>)
+                           8 +  // option_1_pool
+                           8 +  // option_2_pool
+                           8 +  // option_3_pool
+                           8 +  // option_4_pool
+                           8 +  // total_pool
+                           32 + // market_id
+                           20 + // category
+                           1 +  // tracks_creator_quota
+                           8 +  // creator_bond
+                           8 +  // total_claimed
+                           8 +  // total_refunded
+                           8 +  // volume
+                           4 +  // bettor_count
+                           8 +  // fees_collected
+                           8 +  // creator_fees_accrued
+                           8 +  // max_user_stake
+                           2 +  // max_option_share_bps
+                           8 +  // subsidy_bonus
+                           32 + // subsidy_seed
+                           8 +  // subsidy_total
+                           8 +  // subsidy_refunded
+                           16 + // sponsor_fee_per_share
+                           8 +  // creator_stake
+                           8 +  // pools_slot
+                           32;  // settled_pools
+
+    /// Active or Paused: not yet resolved or cancelled
+    pub fn is_live(&self) -> bool {
+        matches!(self.status, MarketStatus::Active | MarketStatus::Paused)
+    }
+
+    /// Checks a position of `position` tokens on `option_index` against the
+    /// exposure caps once a bet of `amount` has landed. Only the first stake
+    /// on an option, into a pool holding nothing but sponsor seed, is exempt
+    /// from the share cap so an empty option can still be opened.
+    pub fn validate_exposure(&self, option_index: u8, position: u64, amount: u64) -> Result<()> {
+        if self.max_user_stake > 0 {
+            require!(position <= self.max_user_stake, ErrorCode::PositionLimitExceeded);
+        }
+        let option_pool = self.option_pool(option_index)?;
+        let pool_before = security_checks::safe_sub(option_pool, amount)?;
+        let is_first_stake = pool_before == self.subsidy_seed[option_index as usize];
+        if self.max_option_share_bps > 0 && !is_first_stake {
+            let share_cap = security_checks::safe_div(
+                security_checks::safe_mul(option_pool, self.max_option_share_bps as u64)?,
+                10000
+            )?;
+            require!(position <= share_cap, ErrorCode::OptionShareExceeded);
+        }
+        Ok(())
+    }
+
+    pub fn option_name(&self, option_index: u8) -> Result<String> {
+        let raw = match option_index {
+            0 => &self.option_1,
+            1 => &self.option_2,
+            2 => &self.option_3,
+            3 => &self.option_4,
+            _ => return Err(ErrorCode::InvalidOptionIndex.into()),
+        };
+        let len = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+        Ok(String::from_utf8_lossy(&raw[..len]).into_owned())
+    }
+
+    pub fn option_pool(&self, option_index: u8) -> Result<u64> {
+        match option_index {
+            0 => Ok(self.option_1_pool),
+            1 => Ok(self.option_2_pool),
+            2 => Ok(self.option_3_pool),
+            3 => Ok(self.option_4_pool),
+            _ => Err(ErrorCode::InvalidOptionIndex.into()),
+        }
+    }
+
+    /// Remembers the option pools as they stand before the first change in
+    /// `slot`, so anything reading them can ignore changes made in its own slot
+    pub fn record_pool_change(&mut self, slot: u64) {
+        if slot > self.pools_slot {
+            self.settled_pools = self.option_pools();
+            self.pools_slot = slot;
+        }
+    }
+
+    /// Option pools as they stood before any change made in `slot`
+    pub fn settled_option_pools(&self, slot: u64) -> [u64; 4] {
+        if slot > self.pools_slot {
+            self.option_pools()
+        } else {
+            self.settled_pools
+        }
+    }
+
+    pub fn option_pools(&self) -> [u64; 4] {
+        [self.option_1_pool, self.option_2_pool, self.option_3_pool, self.option_4_pool]
+    }
+
+    /// Adds `amount` to an option pool and the total pool
+    pub fn credit_option(&mut self, option_index: u8, amount: u64) -> Result<()> {
+        let pool = match option_index {
+            0 => &mut self.option_1_pool,
+            1 => &mut self.option_2_pool,
+            2 => &mut self.option_3_pool,
+            3 => &mut self.option_4_pool,
+            _ => return Err(ErrorCode::InvalidOptionIndex.into()),
+        };
+        *pool = security_checks::safe_add(*pool, amount)?;
+        self.total_pool = security_checks::safe_add(self.total_pool, amount)?;
+        Ok(())
+    }
+
+    /// User stakes on an option, excluding sponsor seed
+    pub fn option_stake(&self, option_index: u8) -> Result<u64> {
+        security_checks::safe_sub(
+            self.option_pool(option_index)?,
+            self.subsidy_seed[option_index as usize]
+        )
+    }
+
+    /// Everything winners share: the pool, seed included, plus the bonus
+    pub fn payout_pool(&self) -> Result<u64> {
+        security_checks::safe_add(self.total_pool, self.subsidy_bonus)
+    }
+
+    /// Spreads the sponsors' fee share over the subsidy
+    pub fn accrue_sponsor_fees(&mut self, fee: u64) -> Result<()> {
+        require!(self.subsidy_total > 0, ErrorCode::DivisionByZero);
+        let per_share = (fee as u128)
+            .checked_mul(Sponsorship::FEE_SCALE)
+            .ok_or(ErrorCode::CalculationError)?
+            / self.subsidy_total as u128;
+        self.sponsor_fee_per_share = self.sponsor_fee_per_share
+            .checked_add(per_share)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    /// Subsidy goes back to sponsors when the market is cancelled or
+    /// resolves to an option nobody bet on
+    pub fn subsidy_refundable(&self) -> Result<bool> {
+        Ok(match (&self.status, self.winning_option) {
+            (MarketStatus::Cancelled, _) => true,
+            (MarketStatus::Resolved, Some(winning_option)) => self.option_stake(winning_option)? == 0,
+            _ => false,
+        })
+    }
+
+    /// Amount the escrow still owes to bettors, sponsors and the creator
+    pub fn escrow_liability(&self) -> Result<u64> {
+        let paid_out = match self.status {
+            MarketStatus::Resolved => self.total_claimed,
+            MarketStatus::Cancelled => self.total_refunded,
+            _ => 0,
+        };
+        let outstanding_pool = security_checks::safe_sub(
+            self.payout_pool()?,
+            security_checks::safe_add(paid_out, self.subsidy_refunded)?
+        )?;
+        security_checks::safe_add(
+            outstanding_pool,
+            security_checks::safe_add(self.creator_bond, self.creator_stake)?
+        )
+    }
 }
 
-impl Market {
-    // Fixed size allocation
-    pub const LEN: usize = 32 + // authority
-                           32 + // creator
-                           33 + // market_type (enum with padding)
-                           200 + // question
-                           2 +  // question_len
-                           50 + // option_1
-                           50 + // option_2
-                           50 + // option_3
-                           50 + // option_4
-                           1 +  // option_count
-                           8 +  // resolution_date
-                           8 +  // creator_fee_rate
-                           8 +  // min_bet_amount
-                           32 + // token_mint
-                           33 + // status (enum with padding)
-                           2 +  // winning_option (Option<u8>)
-                           8 +  // option_1_pool
-                           8 +  // option_2_pool
-                           8 +  // option_3_pool
-                           8 +  // option_4_pool
-                           8 +  // total_pool
-                           32 + // market_id
-                           20;  // category
+/// Owed vs. held balance of a market escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowBalance {
+    pub owed: u64,
+    pub held: u64,
+    pub surplus: u64,
 }
 
 #[account]
@@ -346,6 +1611,114 @@ impl Prediction {
                            1;   // claimed
 }
 
+/// One user's betting history in one collateral mint
+#[account]
+pub struct UserStats {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub total_volume: u64,          // Gross amount bet in this mint, in mint units
+    pub bet_count: u32,
+    pub last_bet_at: i64,
+}
+
+impl UserStats {
+    pub const LEN: usize = 32 + // user
+                           32 + // mint
+                           8 +  // total_volume
+                           4 +  // bet_count
+                           8;   // last_bet_at
+}
+
+#[account]
+pub struct BurnReceipt {
+    pub sequence: u64,              // Per-mint, see CollateralConfig::burn_sequence
+    pub mint: Pubkey,
+    pub burn_type: BurnType,
+    pub user: Pubkey,               // Burner, who also paid the receipt's rent
+    pub market: Pubkey,
+    pub nonce: u64,                 // Caller-chosen, with market and user derives the address
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub burned_at: i64,
+    pub acknowledged: bool,
+    pub acknowledged_at: i64,
+    pub relayer: Pubkey,            // Relayer that acknowledged the burn
+    pub proof_id: [u8; 64],         // BelieveApp proof id
+    pub proof_id_len: u8,
+}
+
+impl BurnReceipt {
+    pub const LEN: usize = 8 +  // sequence
+                           32 + // mint
+                           1 +  // burn_type
+                           32 + // user
+                           32 + // market
+                           8 +  // nonce
+                           8 +  // amount
+                           8 +  // burn_amount
+                           8 +  // burned_at
+                           1 +  // acknowledged
+                           8 +  // acknowledged_at
+                           32 + // relayer
+                           64 + // proof_id
+                           1;   // proof_id_len
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        sequence: u64,
+        mint: Pubkey,
+        burn_type: BurnType,
+        user: Pubkey,
+        market: Pubkey,
+        nonce: u64,
+        amount: u64,
+        burn_amount: u64,
+    ) -> Result<()> {
+        self.sequence = sequence;
+        self.mint = mint;
+        self.burn_type = burn_type;
+        self.user = user;
+        self.market = market;
+        self.nonce = nonce;
+        self.amount = amount;
+        self.burn_amount = burn_amount;
+        self.burned_at = Clock::get()?.unix_timestamp;
+        self.acknowledged = false;
+        self.acknowledged_at = 0;
+        self.relayer = Pubkey::default();
+        self.proof_id = [0u8; 64];
+        self.proof_id_len = 0;
+        Ok(())
+    }
+}
+
+/// The pool buyback_and_burn must use for one input mint, and the worst
+/// price it may accept there. Set by the authority, so a keeper can neither
+/// route the treasury through another pool nor accept a price a pool moved
+/// within the same transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BuybackPool {
+    pub input_mint: Pubkey,
+    pub pool: Pubkey,           // Meteora pool pairing input_mint with the platform token
+    pub min_price: u64,         // Least platform token base units per whole input token
+}
+
+impl BuybackPool {
+    pub const LEN: usize = 32 + // input_mint
+                           32 + // pool
+                           8;   // min_price
+    pub const MAX_POOLS: usize = 4;
+
+    /// Least output the floor allows for `amount_in` of an input mint with
+    /// `decimals`, rounded up
+    pub fn min_output(&self, amount_in: u64, decimals: u8) -> Result<u64> {
+        let scale = security_checks::scale_to_mint_units(1, decimals)? as u128;
+        let min_output = (amount_in as u128 * self.min_price as u128).div_ceil(scale);
+        u64::try_from(min_output).map_err(|_| ErrorCode::CalculationError.into())
+    }
+}
+
 #[account]
 pub struct PlatformState {
     pub authority: Pubkey,
@@ -354,9 +1727,92 @@ pub struct PlatformState {
     pub bet_burn_rate: u64,    // In basis points (1% = 100)
     pub claim_burn_rate: u64,  // In basis points (1% = 100)
     pub platform_fee_rate: u64, // In basis points (1% = 100)
-    pub total_burned: u64,
+    pub total_burned: u64,              // Platform token totals as of the last sync_platform_stats
     pub total_volume: u64,
-    pub is_paused: bool,
+    pub pause_flags: u8,                // Bitflags, see PlatformState::PAUSE_*
+    pub permissionless_creation: bool,  // Anyone may create markets by posting a bond
+    pub permissionless_bond: u64,       // Minimum creator stake for permissionless markets
+    pub burn_relayer: Pubkey,           // BelieveApp relayer allowed to acknowledge burns
+    pub fee_splits: Vec<FeeSplit>,      // How bet and claim fees are shared out
+    pub fee_tiers: Vec<FeeTier>,        // Volume discounts, ordered by min_volume
+    pub pmp_mint: Pubkey,               // PRIMAPE points (Token-2022) mint
+    pub points_tiers: Vec<PointsTier>,  // Points discounts, ordered by min_balance
+    pub buyback_crank: Pubkey,          // Keeper allowed to trigger buyback_and_burn
+    pub buyback_pools: Vec<BuybackPool>, // Pinned buyback pool and price floor per input mint
+}
+
+impl PlatformState {
+    pub const LEN: usize = 32 + // authority
+                           32 + // token_mint
+                           32 + // treasury
+                           8 +  // bet_burn_rate
+                           8 +  // claim_burn_rate
+                           8 +  // platform_fee_rate
+                           8 +  // total_burned
+                           8 +  // total_volume
+                           1 +  // pause_flags
+                           1 +  // permissionless_creation
+                           8 +  // permissionless_bond
+                           32 + // burn_relayer
+                           4 + (FeeSplit::LEN * FeeSplit::MAX_SPLITS) + // vec len + fee_splits
+                           4 + (FeeTier::LEN * FeeTier::MAX_TIERS) +    // vec len + fee_tiers
+                           32 + // pmp_mint
+                           4 + (PointsTier::LEN * PointsTier::MAX_TIERS) + // vec len + points_tiers
+                           32 + // buyback_crank
+                           4 + (BuybackPool::LEN * BuybackPool::MAX_POOLS); // vec len + buyback_pools
+
+    pub const PAUSE_CREATION: u8 = 1 << 0;   // create_market, approve_market
+    pub const PAUSE_BETTING: u8 = 1 << 1;    // place_prediction, sponsor_market
+    pub const PAUSE_RESOLUTION: u8 = 1 << 2; // resolve_market, cancel_market, reject_market
+    pub const PAUSE_PAYOUTS: u8 = 1 << 3;    // claim_reward, release_creator_bond
+    pub const PAUSE_REFUNDS: u8 = 1 << 4;    // emergency_withdraw, withdraw_sponsorship
+    pub const PAUSE_ALL: u8 = Self::PAUSE_CREATION
+        | Self::PAUSE_BETTING
+        | Self::PAUSE_RESOLUTION
+        | Self::PAUSE_PAYOUTS
+        | Self::PAUSE_REFUNDS;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Platform fee and bet burn rates for a user whose cumulative volume in
+    /// the bet's collateral is `volume_tokens` whole tokens
+    pub fn bet_rates_for(&self, volume_tokens: u64) -> (u64, u64) {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume_tokens >= tier.min_volume)
+            .map(|tier| (tier.platform_fee_rate, tier.bet_burn_rate))
+            .unwrap_or((self.platform_fee_rate, self.bet_burn_rate))
+    }
+
+    /// Bet-time platform fee and burn rates: the user's volume tier first,
+    /// then the collateral's own caps, then the points discount off the fee
+    pub fn bet_rates(
+        &self,
+        volume_tokens: u64,
+        collateral_config: &CollateralConfig,
+        points_discount_bps: u16,
+    ) -> Result<(u64, u64)> {
+        let (platform_fee_rate, bet_burn_rate) = self.bet_rates_for(volume_tokens);
+        let (platform_fee_rate, bet_burn_rate) = collateral_config.bet_rates(platform_fee_rate, bet_burn_rate);
+        Ok((PointsTier::discounted_rate(platform_fee_rate, points_discount_bps)?, bet_burn_rate))
+    }
+
+    pub fn buyback_pool_for(&self, input_mint: &Pubkey) -> Option<&BuybackPool> {
+        self.buyback_pools.iter().find(|pool| pool.input_mint == *input_mint)
+    }
+
+    /// Platform fee discount, in basis points, for a PMP points balance
+    pub fn points_discount_for(&self, balance: u64) -> u16 {
+        self.points_tiers
+            .iter()
+            .rev()
+            .find(|tier| balance >= tier.min_balance)
+            .map(|tier| tier.discount_bps)
+            .unwrap_or(0)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -367,47 +1823,646 @@ pub enum MarketType {
     Timeline,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum MarketStatus {
-    Active,
-    Resolved,
-    Cancelled,
-}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MarketStatus {
+    Active,
+    Resolved,
+    Cancelled,
+    PendingReview,
+    Paused,
+}
+
+#[program]
+pub mod market_system {
+    use super::*;
+
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        bet_burn_rate: u64,
+        claim_burn_rate: u64,
+        platform_fee_rate: u64,
+    ) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        
+        // Initialize platform state
+        platform_state.authority = ctx.accounts.authority.key();
+        platform_state.token_mint = ctx.accounts.token_mint.key();
+        platform_state.treasury = ctx.accounts.treasury.key();
+        platform_state.bet_burn_rate = bet_burn_rate;
+        platform_state.claim_burn_rate = claim_burn_rate;
+        platform_state.platform_fee_rate = platform_fee_rate;
+        platform_state.total_burned = 0;
+        platform_state.total_volume = 0;
+        platform_state.pause_flags = 0;
+        platform_state.permissionless_creation = false;
+        platform_state.permissionless_bond = 0;
+        platform_state.burn_relayer = Pubkey::default();
+        platform_state.fee_splits = FeeSplit::defaults();
+        platform_state.fee_tiers = Vec::new();
+        platform_state.pmp_mint = Pubkey::default();
+        platform_state.points_tiers = Vec::new();
+        platform_state.buyback_crank = Pubkey::default();
+        platform_state.buyback_pools = Vec::new();
+        
+        msg!("Platform initialized!");
+        msg!("Authority: {}", platform_state.authority);
+        msg!("Token mint: {}", platform_state.token_mint);
+        msg!("Treasury: {}", platform_state.treasury);
+        msg!("Bet burn rate: {}bp", bet_burn_rate);
+        msg!("Claim burn rate: {}bp", claim_burn_rate);
+        msg!("Platform fee rate: {}bp", platform_fee_rate);
+        
+        emit!(PlatformInitialized {
+            authority: platform_state.authority,
+            token_mint: platform_state.token_mint,
+            treasury: platform_state.treasury,
+            bet_burn_rate,
+            claim_burn_rate,
+            platform_fee_rate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_permissionless_creation(
+        ctx: Context<UpdatePlatform>,
+        enabled: bool,
+        bond_amount: u64,
+    ) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.permissionless_creation = enabled;
+        platform_state.permissionless_bond = bond_amount;
+        
+        msg!("Permissionless creation: {}", enabled);
+        msg!("Required bond: {} tokens", bond_amount);
+        
+        emit!(PermissionlessCreationUpdated {
+            authority: ctx.accounts.authority.key(),
+            enabled,
+            bond_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_burn_relayer(ctx: Context<UpdatePlatform>, relayer: Pubkey) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.burn_relayer = relayer;
+        
+        msg!("Burn relayer: {}", relayer);
+        
+        emit!(BurnRelayerUpdated {
+            authority: ctx.accounts.authority.key(),
+            relayer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_buyback_crank(ctx: Context<UpdatePlatform>, crank: Pubkey) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.buyback_crank = crank;
+        
+        msg!("Buyback crank: {}", crank);
+        
+        emit!(BuybackCrankUpdated {
+            authority: ctx.accounts.authority.key(),
+            crank,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pins the pool buyback_and_burn uses for `input_mint` and the least
+    /// platform tokens, in base units, it may accept per whole input token. A
+    /// default `pool` removes the mint, so its fees can no longer be bought back.
+    pub fn set_buyback_pool(
+        ctx: Context<UpdatePlatform>,
+        input_mint: Pubkey,
+        pool: Pubkey,
+        min_price: u64,
+    ) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        require!(input_mint != platform_state.token_mint, ErrorCode::InvalidMint);
+        platform_state.buyback_pools.retain(|entry| entry.input_mint != input_mint);
+        if pool != Pubkey::default() {
+            require!(
+                min_price > 0 && platform_state.buyback_pools.len() < BuybackPool::MAX_POOLS,
+                ErrorCode::InvalidBuybackPool
+            );
+            platform_state.buyback_pools.push(BuybackPool { input_mint, pool, min_price });
+        }
+        
+        msg!("Buyback pool for {}: {}", input_mint, pool);
+        msg!("Min price: {} per whole input token", min_price);
+        
+        emit!(BuybackPoolUpdated {
+            authority: ctx.accounts.authority.key(),
+            input_mint,
+            pool,
+            min_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn acknowledge_burn(
+        ctx: Context<AcknowledgeBurn>,
+        proof_id: [u8; 64],
+        proof_id_len: u8,
+    ) -> Result<()> {
+        require!(
+            proof_id_len > 0 && proof_id_len as usize <= proof_id.len(),
+            ErrorCode::InvalidProofId
+        );
+        let timestamp = Clock::get()?.unix_timestamp;
+        let burn_receipt = &mut ctx.accounts.burn_receipt;
+        burn_receipt.acknowledged = true;
+        burn_receipt.acknowledged_at = timestamp;
+        burn_receipt.relayer = ctx.accounts.relayer.key();
+        burn_receipt.proof_id = [0u8; 64];
+        burn_receipt.proof_id[..proof_id_len as usize].copy_from_slice(&proof_id[..proof_id_len as usize]);
+        burn_receipt.proof_id_len = proof_id_len;
+        
+        msg!("Burn {} acknowledged", burn_receipt.sequence);
+        msg!("Proof ID: {}", String::from_utf8_lossy(&burn_receipt.proof_id[..proof_id_len as usize]));
+        
+        emit!(BurnAcknowledged {
+            receipt: burn_receipt.key(),
+            sequence: burn_receipt.sequence,
+            relayer: burn_receipt.relayer,
+            burn_amount: burn_receipt.burn_amount,
+            proof_id: burn_receipt.proof_id,
+            proof_id_len,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Closes an acknowledged receipt once the relayer has recorded it and
+    /// refunds the rent to the burner who paid for it
+    pub fn close_burn_receipt(ctx: Context<CloseBurnReceipt>) -> Result<()> {
+        let burn_receipt = &ctx.accounts.burn_receipt;
+        
+        msg!("Burn receipt {} closed", burn_receipt.sequence);
+        
+        emit!(BurnReceiptClosed {
+            receipt: burn_receipt.key(),
+            sequence: burn_receipt.sequence,
+            payer: ctx.accounts.payer.key(),
+            relayer: ctx.accounts.relayer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_fee_splits(ctx: Context<UpdatePlatform>, fee_splits: Vec<FeeSplit>) -> Result<()> {
+        FeeSplit::validate(&fee_splits)?;
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.fee_splits = fee_splits;
+        
+        for split in platform_state.fee_splits.iter() {
+            msg!("Fee split {:?}: bet {}bp, claim {}bp", split.recipient, split.bet_bps, split.claim_bps);
+        }
+        
+        emit!(FeeSplitsUpdated {
+            authority: ctx.accounts.authority.key(),
+            fee_splits: platform_state.fee_splits.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_fee_tiers(ctx: Context<UpdatePlatform>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        FeeTier::validate(&fee_tiers, platform_state.platform_fee_rate, platform_state.bet_burn_rate)?;
+        platform_state.fee_tiers = fee_tiers;
+        
+        for tier in platform_state.fee_tiers.iter() {
+            msg!(
+                "Fee tier from {}: fee {}bp, burn {}bp",
+                tier.min_volume, tier.platform_fee_rate, tier.bet_burn_rate
+            );
+        }
+        
+        emit!(FeeTiersUpdated {
+            authority: ctx.accounts.authority.key(),
+            fee_tiers: platform_state.fee_tiers.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_points_discounts(
+        ctx: Context<UpdatePlatform>,
+        pmp_mint: Pubkey,
+        points_tiers: Vec<PointsTier>,
+    ) -> Result<()> {
+        PointsTier::validate(&points_tiers)?;
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.pmp_mint = pmp_mint;
+        platform_state.points_tiers = points_tiers;
+        
+        msg!("PMP mint: {}", pmp_mint);
+        for tier in platform_state.points_tiers.iter() {
+            msg!("Points tier from {}: {}bp off platform fee", tier.min_balance, tier.discount_bps);
+        }
+        
+        emit!(PointsDiscountsUpdated {
+            authority: ctx.accounts.authority.key(),
+            pmp_mint,
+            points_tiers: platform_state.points_tiers.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_collateral_config(
+        ctx: Context<SetCollateralConfig>,
+        enabled: bool,
+        min_bet_tokens: u64,
+        burn_enabled: bool,
+        platform_fee_rate: u64,
+        max_bet_tokens: u64,
+        max_pool_tokens: u64,
+    ) -> Result<()> {
+        security_checks::validate_fee_rate(platform_fee_rate)?;
+        require!(
+            max_bet_tokens > 0 && max_bet_tokens <= max_pool_tokens,
+            ErrorCode::InvalidAmount
+        );
+        require!(min_bet_tokens <= max_bet_tokens, ErrorCode::InvalidAmount);
+        // Both caps must be representable in the mint's base units
+        let decimals = ctx.accounts.token_mint.decimals;
+        security_checks::scale_to_mint_units(max_pool_tokens, decimals)?;
+        let collateral_config = &mut ctx.accounts.collateral_config;
+        collateral_config.mint = ctx.accounts.token_mint.key();
+        collateral_config.decimals = ctx.accounts.token_mint.decimals;
+        collateral_config.enabled = enabled;
+        collateral_config.min_bet_tokens = min_bet_tokens;
+        collateral_config.burn_enabled = burn_enabled;
+        collateral_config.platform_fee_rate = platform_fee_rate;
+        collateral_config.max_bet_tokens = max_bet_tokens;
+        collateral_config.max_pool_tokens = max_pool_tokens;
+        
+        msg!("Collateral {}: enabled {}", collateral_config.mint, enabled);
+        msg!("Min bet: {} whole tokens", min_bet_tokens);
+        msg!("Burns enabled: {}", burn_enabled);
+        msg!("Platform fee cap: {}bp", platform_fee_rate);
+        msg!("Max bet: {} whole tokens, max pool: {} whole tokens", max_bet_tokens, max_pool_tokens);
+        
+        emit!(CollateralConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            mint: collateral_config.mint,
+            enabled,
+            min_bet_tokens,
+            burn_enabled,
+            platform_fee_rate,
+            max_bet_tokens,
+            max_pool_tokens,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_pause_flags(ctx: Context<UpdatePlatform>, pause_flags: u8) -> Result<()> {
+        require!(
+            pause_flags & !PlatformState::PAUSE_ALL == 0,
+            ErrorCode::InvalidPauseFlags
+        );
+        let platform_state = &mut ctx.accounts.platform_state;
+        let previous_flags = platform_state.pause_flags;
+        platform_state.pause_flags = pause_flags;
+        
+        msg!("Pause flags updated: {:#07b}", pause_flags);
+        msg!("Creation paused: {}", platform_state.is_paused(PlatformState::PAUSE_CREATION));
+        msg!("Betting paused: {}", platform_state.is_paused(PlatformState::PAUSE_BETTING));
+        msg!("Resolution paused: {}", platform_state.is_paused(PlatformState::PAUSE_RESOLUTION));
+        msg!("Payouts paused: {}", platform_state.is_paused(PlatformState::PAUSE_PAYOUTS));
+        msg!("Refunds paused: {}", platform_state.is_paused(PlatformState::PAUSE_REFUNDS));
+        
+        emit!(PauseFlagsUpdated {
+            authority: ctx.accounts.authority.key(),
+            previous_flags,
+            pause_flags,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Refreshes the platform totals from the platform token's collateral
+    /// counters. Permissionless: it only copies on-chain state.
+    pub fn sync_platform_stats(ctx: Context<SyncPlatformStats>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let collateral_config = &ctx.accounts.collateral_config;
+        platform_state.total_volume = collateral_config.total_volume;
+        platform_state.total_burned = collateral_config.total_burned;
+        
+        msg!("Platform volume: {} tokens", platform_state.total_volume);
+        msg!("Platform burned: {} tokens", platform_state.total_burned);
+        
+        emit!(PlatformStatsSynced {
+            total_volume: platform_state.total_volume,
+            total_burned: platform_state.total_burned,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        fee_ledger.mint = ctx.accounts.token_mint.key();
+        fee_ledger.treasury_accrued = 0;
+        fee_ledger.stakers_accrued = 0;
+        fee_ledger.creators_accrued = 0;
+        
+        msg!("Treasury vault initialized: {}", ctx.accounts.treasury_vault.key());
+        msg!("Mint: {}", ctx.accounts.token_mint.key());
+        
+        emit!(TreasuryVaultInitialized {
+            authority: ctx.accounts.authority.key(),
+            mint: ctx.accounts.token_mint.key(),
+            vault: ctx.accounts.treasury_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Sends the treasury's accrued fee share from the vault to the configured treasury
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let vault = &ctx.accounts.treasury_vault;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= vault.amount, ErrorCode::InsufficientTreasuryBalance);
+        require!(amount <= fee_ledger.treasury_accrued, ErrorCode::InsufficientAccruedFees);
+        fee_ledger.treasury_accrued -= amount;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    vault.mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        emit!(TreasuryWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            mint: vault.mint,
+            destination: ctx.accounts.treasury_token_account.key(),
+            amount,
+            remaining: fee_ledger.treasury_accrued,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Treasury withdrawal: {} tokens", amount);
+        Ok(())
+    }
+
+    /// Swaps `amount_in` of the treasury's accrued share of another mint (e.g.
+    /// USDC or wrapped SOL) into the platform token through Meteora, failing
+    /// if fewer than `minimum_amount_out` tokens arrive, and burns all of it.
+    /// The pool must be the one the authority pinned for the input mint, and
+    /// `minimum_amount_out` must meet that pin's `min_price`. The floor comes
+    /// from the authority rather than the pool's reserves, which the caller
+    /// could move earlier in the same transaction.
+    pub fn buyback_and_burn(
+        ctx: Context<BuybackAndBurn>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        burn_nonce: u64,
+    ) -> Result<()> {
+        let fee_ledger = &mut ctx.accounts.input_fee_ledger;
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        require!(amount_in <= ctx.accounts.input_vault.amount, ErrorCode::InsufficientTreasuryBalance);
+        require!(amount_in <= fee_ledger.treasury_accrued, ErrorCode::InsufficientAccruedFees);
+        fee_ledger.treasury_accrued -= amount_in;
+        
+        let input_mint = ctx.accounts.input_mint.key();
+        let buyback_pool = ctx.accounts.platform_state
+            .buyback_pool_for(&input_mint)
+            .ok_or(ErrorCode::BuybackPoolNotSet)?;
+        require!(buyback_pool.pool == ctx.accounts.meteora.pool.key(), ErrorCode::BuybackPoolNotSet);
+        let min_allowed = buyback_pool.min_output(amount_in, ctx.accounts.input_mint.decimals)?;
+        require!(minimum_amount_out >= min_allowed, ErrorCode::BuybackMinimumTooLow);
+        
+        let burn_amount = meteora::meteora_swap(
+            &ctx.accounts.meteora,
+            ctx.accounts.input_vault.to_account_info(),
+            ctx.accounts.treasury_vault.to_account_info(),
+            ctx.accounts.input_vault.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
+            amount_in,
+            minimum_amount_out,
+            &[&[
+                b"treasury_vault",
+                input_mint.as_ref(),
+                &[ctx.bumps.input_vault],
+            ]],
+        )?;
+        
+        let token_mint = ctx.accounts.token_mint.key();
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    authority: ctx.accounts.treasury_vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    token_mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            burn_amount,
+        )?;
+        
+        let collateral_config = &mut ctx.accounts.collateral_config;
+        collateral_config.record_activity(0, burn_amount)?;
+        let caller = ctx.accounts.caller.key();
+        if let Some(sequence) = record_burn(
+            collateral_config,
+            ctx.accounts.burn_receipt.as_mut(),
+            BurnType::Buyback,
+            caller,
+            Pubkey::default(),
+            burn_nonce,
+            amount_in,
+            burn_amount,
+        )? {
+            believeapp_integration::process_buyback_burn(sequence, token_mint, caller, amount_in, burn_amount)?;
+        }
+        
+        msg!("Buyback: {} input tokens for {} burned tokens", amount_in, burn_amount);
+        
+        emit!(BuybackExecuted {
+            caller,
+            input_mint,
+            pool: ctx.accounts.meteora.pool.key(),
+            amount_in,
+            burn_amount,
+            treasury_remaining: fee_ledger.treasury_accrued,
+            total_burned: collateral_config.total_burned,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Sends the stakers' accrued fee share to a staker rewards account
+    pub fn withdraw_staker_fees(ctx: Context<WithdrawStakerFees>, amount: u64) -> Result<()> {
+        let vault = &ctx.accounts.treasury_vault;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= fee_ledger.stakers_accrued, ErrorCode::InsufficientAccruedFees);
+        fee_ledger.stakers_accrued -= amount;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.staker_rewards_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    vault.mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        emit!(StakerFeesWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            mint: vault.mint,
+            destination: ctx.accounts.staker_rewards_account.key(),
+            amount,
+            remaining: fee_ledger.stakers_accrued,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Staker fee withdrawal: {} tokens", amount);
+        Ok(())
+    }
+
+    /// Pays the market creator their accrued fee share from the treasury vault
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
+        );
+        let market = &mut ctx.accounts.market;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        let amount = market.creator_fees_accrued;
+        require!(amount > 0, ErrorCode::InsufficientAccruedFees);
+        
+        market.creator_fees_accrued = 0;
+        fee_ledger.creators_accrued = security_checks::safe_sub(fee_ledger.creators_accrued, amount)?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury_vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    market.token_mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("Creator fees claimed for market: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Amount: {} tokens", amount);
+        
+        emit!(CreatorFeesClaimed {
+            market: market.key(),
+            creator: market.creator,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-#[program]
-pub mod market_system {
-    use super::*;
-    use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-    use anchor_lang::solana_program::program::invoke;
-    use anchor_lang::solana_program::system_instruction;
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+        referrer_rewards.referrer = ctx.accounts.referrer.key();
+        referrer_rewards.mint = ctx.accounts.token_mint.key();
+        referrer_rewards.accrued = 0;
+        referrer_rewards.total_earned = 0;
+        referrer_rewards.referral_count = 0;
+        
+        msg!("Referrer registered: {}", referrer_rewards.referrer);
+        msg!("Mint: {}", referrer_rewards.mint);
+        
+        emit!(ReferrerRegistered {
+            referrer: referrer_rewards.referrer,
+            mint: referrer_rewards.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-    pub fn initialize(
-        ctx: Context<Initialize>,
-        bet_burn_rate: u64,
-        claim_burn_rate: u64,
-        platform_fee_rate: u64,
-    ) -> Result<()> {
-        let platform_state = &mut ctx.accounts.platform_state;
+    /// Pays a referrer their accrued share of platform fees from the treasury vault
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
+        );
+        let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        let amount = referrer_rewards.accrued;
+        require!(amount > 0, ErrorCode::InsufficientAccruedFees);
         
-        // Initialize platform state
-        platform_state.authority = ctx.accounts.authority.key();
-        platform_state.token_mint = ctx.accounts.token_mint.key();
-        platform_state.treasury = ctx.accounts.treasury.key();
-        platform_state.bet_burn_rate = bet_burn_rate;
-        platform_state.claim_burn_rate = claim_burn_rate;
-        platform_state.platform_fee_rate = platform_fee_rate;
-        platform_state.total_burned = 0;
-        platform_state.total_volume = 0;
-        platform_state.is_paused = false;
+        referrer_rewards.accrued = 0;
+        fee_ledger.referrers_accrued = security_checks::safe_sub(fee_ledger.referrers_accrued, amount)?;
         
-        msg!("Platform initialized!");
-        msg!("Authority: {}", platform_state.authority);
-        msg!("Token mint: {}", platform_state.token_mint);
-        msg!("Treasury: {}", platform_state.treasury);
-        msg!("Bet burn rate: {}bp", bet_burn_rate);
-        msg!("Claim burn rate: {}bp", claim_burn_rate);
-        msg!("Platform fee rate: {}bp", platform_fee_rate);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.referrer_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury_vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    referrer_rewards.mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("Referral rewards claimed: {} tokens", amount);
         
+        emit!(ReferralRewardsClaimed {
+            referrer: referrer_rewards.referrer,
+            mint: referrer_rewards.mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -415,6 +2470,11 @@ pub mod market_system {
         let access_control = &mut ctx.accounts.access_control;
         access_control.admin = ctx.accounts.admin.key();
         access_control.market_creators = Vec::new();
+        
+        emit!(AccessControlInitialized {
+            admin: access_control.admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -429,6 +2489,13 @@ pub mod market_system {
             ErrorCode::Unauthorized
         );
         access_control.market_creators.push(new_creator);
+        
+        emit!(MarketCreatorAdded {
+            admin: ctx.accounts.admin.key(),
+            creator: new_creator,
+            creator_count: access_control.market_creators.len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -440,9 +2507,57 @@ pub mod market_system {
             .position(|&x| x == creator)
             .ok_or(ErrorCode::Unauthorized)?;
         access_control.market_creators.remove(index);
+        
+        emit!(MarketCreatorRemoved {
+            admin: ctx.accounts.admin.key(),
+            creator,
+            creator_count: access_control.market_creators.len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_creator_config(
+        ctx: Context<SetCreatorConfig>,
+        creator: Pubkey,
+        max_fee_rate: u64,
+        max_active_markets: u16,
+        min_stake_tokens: u64,
+        allowed_categories: Vec<[u8; 20]>,
+    ) -> Result<()> {
+        security_checks::validate_fee_rate(max_fee_rate)?;
+        require!(
+            allowed_categories.len() <= CreatorConfig::MAX_CATEGORIES,
+            ErrorCode::TooManyCategories
+        );
+        
+        let creator_config = &mut ctx.accounts.creator_config;
+        creator_config.creator = creator;
+        creator_config.max_fee_rate = max_fee_rate;
+        creator_config.max_active_markets = max_active_markets;
+        creator_config.min_stake_tokens = min_stake_tokens;
+        creator_config.allowed_categories = allowed_categories;
+        
+        msg!("Creator config updated: {}", creator);
+        msg!("Max fee: {}bp", max_fee_rate);
+        msg!("Max active markets: {}", max_active_markets);
+        msg!("Active markets: {}", creator_config.active_markets);
+        msg!("Min stake: {} whole tokens", min_stake_tokens);
+        
+        emit!(CreatorConfigUpdated {
+            admin: ctx.accounts.admin.key(),
+            creator,
+            max_fee_rate,
+            max_active_markets,
+            active_markets: creator_config.active_markets,
+            min_stake_tokens,
+            allowed_categories: creator_config.allowed_categories.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
         market_type: MarketType,
@@ -464,14 +2579,18 @@ pub mod market_system {
     ) -> Result<()> {
         let ac = &ctx.accounts.access_control;
         let creator = ctx.accounts.creator.key();
-        require!(creator == ac.admin || ac.market_creators.contains(&creator), ErrorCode::Unauthorized);
+        let platform_state = &ctx.accounts.platform_state;
+        let is_whitelisted = creator == ac.admin || ac.market_creators.contains(&creator);
+        require!(is_whitelisted || platform_state.permissionless_creation, ErrorCode::Unauthorized);
         
         let market = &mut ctx.accounts.market;
-        let platform_state = &ctx.accounts.platform_state;
         let current_time = Clock::get()?.unix_timestamp;
         
         // Security validations
-        require!(!platform_state.is_paused, ErrorCode::PlatformPaused);
+        require!(
+            !platform_state.is_paused(PlatformState::PAUSE_CREATION),
+            ErrorCode::CreationPaused
+        );
         require!(option_count >= 2, ErrorCode::InsufficientOptions);
         require!(option_count <= 4, ErrorCode::TooManyOptions);
         
@@ -487,11 +2606,27 @@ pub mod market_system {
         security_checks::validate_string_length(category_len as usize, 20)?;
         
         // Validate stake amount
-        security_checks::validate_bet_amount(creator_stake_amount, min_bet_amount)?;
+        let collateral_config = &ctx.accounts.collateral_config;
+        let decimals = ctx.accounts.token_mint.decimals;
+        security_checks::validate_bet_amount(
+            creator_stake_amount,
+            min_bet_amount,
+            collateral_config.max_bet_amount(decimals)?
+        )?;
+        require!(
+            min_bet_amount >= collateral_config.min_bet_amount(decimals)?,
+            ErrorCode::BetTooSmall
+        );
+        if !is_whitelisted {
+            require!(
+                creator_stake_amount >= platform_state.permissionless_bond,
+                ErrorCode::BondTooLow
+            );
+        }
         
         market.authority = platform_state.authority;
         market.creator = ctx.accounts.creator.key();
-        market.market_type = market_type.clone();
+        market.market_type = market_type;
         
         // Initialize arrays
         market.question = [0u8; 200];
@@ -519,11 +2654,26 @@ pub mod market_system {
         market.market_id[..market_id_len as usize].copy_from_slice(&market_id[..market_id_len as usize]);
         market.category[..category_len as usize].copy_from_slice(&category[..category_len as usize]);
         
+        // Whitelisted creators are bound by their per-creator config
+        market.tracks_creator_quota = false;
+        if is_whitelisted && creator != ac.admin {
+            let creator_config = ctx.accounts.creator_config
+                .as_mut()
+                .ok_or(ErrorCode::CreatorConfigRequired)?;
+            creator_config.open_market(creator_fee_rate, creator_stake_amount, &market.category, decimals)?;
+            market.tracks_creator_quota = true;
+        }
+        
         market.resolution_date = resolution_date;
         market.creator_fee_rate = creator_fee_rate;
         market.min_bet_amount = min_bet_amount;
-        market.token_mint = platform_state.token_mint;
-        market.status = MarketStatus::Active;
+        market.token_mint = ctx.accounts.token_mint.key();
+        // Permissionless markets wait for admin review before accepting bets
+        market.status = if is_whitelisted {
+            MarketStatus::Active
+        } else {
+            MarketStatus::PendingReview
+        };
         market.winning_option = None;
         market.total_pool = 0;
         
@@ -532,199 +2682,625 @@ pub mod market_system {
         market.option_2_pool = 0;
         market.option_3_pool = 0;
         market.option_4_pool = 0;
+        market.volume = 0;
+        market.bettor_count = 0;
+        market.fees_collected = 0;
+        market.creator_fees_accrued = 0;
+        market.max_user_stake = 0;
+        market.max_option_share_bps = 0;
+        market.subsidy_bonus = 0;
+        market.subsidy_seed = [0; 4];
+        market.subsidy_total = 0;
+        market.subsidy_refunded = 0;
+        market.sponsor_fee_per_share = 0;
+        market.pools_slot = 0;
+        market.settled_pools = [0; 4];
         let stake_amount = creator_stake_amount;
         let burn_amount = stake_amount
-            .checked_mul(50)
+            .checked_mul(ctx.accounts.collateral_config.burn_rate(50))
             .ok_or(ErrorCode::CalculationError)?
             .checked_div(10000)
             .ok_or(ErrorCode::CalculationError)?;
-        token::transfer(
+        let escrow_transfer = stake_amount.checked_sub(burn_amount).ok_or(ErrorCode::CalculationError)?;
+        let stake_received = security_checks::safe_sub(
+            escrow_transfer,
+            transfer_fee_for(&ctx.accounts.token_mint, escrow_transfer)?
+        )?;
+        token_interface::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.creator_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.market_escrow.to_account_info(),
                     authority: ctx.accounts.creator.to_account_info(),
                 },
             ),
-            stake_amount.checked_sub(burn_amount).ok_or(ErrorCode::CalculationError)?,
+            escrow_transfer,
+            ctx.accounts.token_mint.decimals,
         )?;
-        token::transfer(
+        token_interface::burn(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     from: ctx.accounts.creator_token_account.to_account_info(),
-                    to: ctx.accounts.burn_token_account.to_account_info(),
                     authority: ctx.accounts.creator.to_account_info(),
                 },
             ),
             burn_amount,
         )?;
         
+        let collateral_config = &mut ctx.accounts.collateral_config;
+        collateral_config.record_activity(0, burn_amount)?;
+        if let Some(sequence) = record_burn(
+            collateral_config,
+            ctx.accounts.burn_receipt.as_mut(),
+            BurnType::MarketCreation,
+            ctx.accounts.creator.key(),
+            market.key(),
+            0,
+            stake_amount,
+            burn_amount,
+        )? {
+            believeapp_integration::process_market_creation_burn(
+                sequence,
+                market.token_mint,
+                ctx.accounts.creator.key(),
+                market.key(),
+                stake_amount,
+                burn_amount,
+            )?;
+        }
+        
+        // Both are returned through release_creator_bond; only the bond of a
+        // market pending review can be slashed
+        if is_whitelisted {
+            market.creator_bond = 0;
+            market.creator_stake = stake_received;
+        } else {
+            market.creator_bond = stake_received;
+            market.creator_stake = 0;
+        }
+        
         let market_id_str = String::from_utf8_lossy(&market.market_id);
         msg!("Market created: {}", market_id_str);
         msg!("Market type: {:?}", market_type);
         msg!("Resolution date: {}", resolution_date);
         msg!("Creator fee: {}bp", creator_fee_rate);
         msg!("Stake burn: {} tokens", burn_amount);
+        if !is_whitelisted {
+            msg!("Creator bond: {} tokens (pending review)", market.creator_bond);
+        }
+        
+        emit!(MarketCreated {
+            market: market.key(),
+            creator,
+            authority: market.authority,
+            market_type,
+            status: market.status.clone(),
+            market_id: market.market_id,
+            category: market.category,
+            option_count,
+            resolution_date,
+            creator_fee_rate,
+            min_bet_amount,
+            token_mint: market.token_mint,
+            stake_amount,
+            burn_amount,
+            creator_bond: market.creator_bond,
+            timestamp: current_time,
+        });
         Ok(())
     }
 
-    pub fn place_prediction(
-        ctx: Context<PlacePrediction>,
-        option_index: u8,
-        amount: u64,
+    /// Sets optional per-user exposure caps on a live market. Zero disables a
+    /// cap; lowering a cap does not affect existing positions, only new bets.
+    pub fn set_market_limits(
+        ctx: Context<SetMarketLimits>,
+        max_user_stake: u64,
+        max_option_share_bps: u16,
     ) -> Result<()> {
+        require!(max_option_share_bps <= 10000, ErrorCode::InvalidMarketLimits);
         let market = &mut ctx.accounts.market;
-        let platform_state = &ctx.accounts.platform_state;
+        require!(
+            max_user_stake == 0 || max_user_stake >= market.min_bet_amount,
+            ErrorCode::InvalidMarketLimits
+        );
         
-        // Security validations
-        require!(!platform_state.is_paused, ErrorCode::PlatformPaused);
-        require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
+        market.max_user_stake = max_user_stake;
+        market.max_option_share_bps = max_option_share_bps;
+        
+        msg!("Market limits updated: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Max user stake: {} tokens", max_user_stake);
+        msg!("Max option share: {}bp", max_option_share_bps);
+        
+        emit!(MarketLimitsUpdated {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            max_user_stake,
+            max_option_share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Deposits sponsor subsidy into a market's escrow, at creation (in the
+    /// same transaction) or any time before it closes. `bonus_amount` is paid
+    /// to winners on top of the pool; `seed_amounts` are added to each option's
+    /// pool to improve early odds and are likewise paid to winners. Any
+    /// transfer fee withheld by the mint comes out of the bonus.
+    pub fn sponsor_market(
+        ctx: Context<SponsorMarket>,
+        bonus_amount: u64,
+        seed_amounts: [u64; 4],
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_BETTING),
+            ErrorCode::BettingPaused
+        );
+        let market = &mut ctx.accounts.market;
         require!(
             Clock::get()?.unix_timestamp < market.resolution_date,
             ErrorCode::MarketClosed
         );
+        let mut seed_total: u64 = 0;
+        for (option_index, seed) in seed_amounts.iter().enumerate() {
+            require!(
+                *seed == 0 || (option_index as u8) < market.option_count,
+                ErrorCode::InvalidOptionIndex
+            );
+            seed_total = security_checks::safe_add(seed_total, *seed)?;
+        }
+        let amount = security_checks::safe_add(bonus_amount, seed_total)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
         
-        // Validate option index
-        security_checks::validate_option_index(option_index, market.option_count)?;
+        let transfer_fee = transfer_fee_for(&ctx.accounts.token_mint, amount)?;
+        require!(transfer_fee <= bonus_amount, ErrorCode::SubsidyBonusTooSmall);
+        let bonus_received = bonus_amount - transfer_fee;
+        security_checks::validate_pool_addition(
+            market.total_pool,
+            seed_total,
+            ctx.accounts.collateral_config.max_pool_amount(ctx.accounts.token_mint.decimals)?
+        )?;
         
-        // Validate bet amount
-        security_checks::validate_bet_amount(amount, market.min_bet_amount)?;
+        // Settle fees earned at the old weight before it changes
+        let sponsorship = &mut ctx.accounts.sponsorship;
+        sponsorship.settle(market.sponsor_fee_per_share)?;
+        sponsorship.market = market.key();
+        sponsorship.sponsor = ctx.accounts.sponsor.key();
+        sponsorship.bonus_amount = security_checks::safe_add(sponsorship.bonus_amount, bonus_received)?;
+        sponsorship.seed_amount = security_checks::safe_add(sponsorship.seed_amount, seed_total)?;
+        sponsorship.rebase(market.sponsor_fee_per_share)?;
         
-        // Calculate fees using safe math
-        let burn_amount = security_checks::safe_div(
-            security_checks::safe_mul(amount, platform_state.bet_burn_rate)?,
-            10000
+        market.record_pool_change(Clock::get()?.slot);
+        for (option_index, seed) in seed_amounts.iter().enumerate() {
+            if *seed > 0 {
+                market.credit_option(option_index as u8, *seed)?;
+                market.subsidy_seed[option_index] = security_checks::safe_add(
+                    market.subsidy_seed[option_index],
+                    *seed
+                )?;
+            }
+        }
+        market.subsidy_bonus = security_checks::safe_add(market.subsidy_bonus, bonus_received)?;
+        market.subsidy_total = security_checks::safe_add(
+            market.subsidy_total,
+            security_checks::safe_add(bonus_received, seed_total)?
         )?;
         
-        let platform_fee = security_checks::safe_div(
-            security_checks::safe_mul(amount, platform_state.platform_fee_rate)?,
-            10000
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.sponsor_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.market_escrow.to_account_info(),
+                    authority: ctx.accounts.sponsor.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
-        let net_amount = security_checks::safe_sub(
-            security_checks::safe_sub(amount, burn_amount)?,
-            platform_fee
+        msg!("Market sponsored: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Bonus: {} tokens, seed: {} tokens", bonus_received, seed_total);
+        
+        emit!(MarketSponsored {
+            market: market.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            bonus_amount: bonus_received,
+            seed_amounts,
+            subsidy_bonus: market.subsidy_bonus,
+            subsidy_total: market.subsidy_total,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pays a sponsor's share of the market's sponsor fees out of the treasury vault
+    pub fn claim_sponsor_fees(ctx: Context<ClaimSponsorFees>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
+        );
+        let market = &ctx.accounts.market;
+        let sponsorship = &mut ctx.accounts.sponsorship;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        sponsorship.settle(market.sponsor_fee_per_share)?;
+        let amount = sponsorship.fees_owed;
+        require!(amount > 0, ErrorCode::InsufficientAccruedFees);
+        
+        sponsorship.fees_owed = 0;
+        sponsorship.fees_claimed = security_checks::safe_add(sponsorship.fees_claimed, amount)?;
+        fee_ledger.sponsors_accrued = security_checks::safe_sub(fee_ledger.sponsors_accrued, amount)?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.sponsor_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury_vault.to_account_info(),
+                },
+                &[&[
+                    b"treasury_vault",
+                    market.token_mint.as_ref(),
+                    &[ctx.bumps.treasury_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
-        // Validate pool addition before any transfers
-        security_checks::validate_pool_addition(market.total_pool, net_amount)?;
+        msg!("Sponsor fees claimed for market: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Amount: {} tokens", amount);
         
-        // Update the correct option pool based on option_index
-        match option_index {
-            0 => {
-                security_checks::validate_pool_addition(market.option_1_pool, net_amount)?;
-                market.option_1_pool = market.option_1_pool
-                    .checked_add(net_amount)
-                    .ok_or(ErrorCode::CalculationError)?;
-            },
-            1 => {
-                security_checks::validate_pool_addition(market.option_2_pool, net_amount)?;
-                market.option_2_pool = market.option_2_pool
-                    .checked_add(net_amount)
-                    .ok_or(ErrorCode::CalculationError)?;
-            },
-            2 => {
-                security_checks::validate_pool_addition(market.option_3_pool, net_amount)?;
-                market.option_3_pool = market.option_3_pool
-                    .checked_add(net_amount)
-                    .ok_or(ErrorCode::CalculationError)?;
-            },
-            3 => {
-                security_checks::validate_pool_addition(market.option_4_pool, net_amount)?;
-                market.option_4_pool = market.option_4_pool
-                    .checked_add(net_amount)
-                    .ok_or(ErrorCode::CalculationError)?;
-            },
-            _ => return Err(ErrorCode::InvalidOptionIndex.into()),
+        emit!(SponsorFeesClaimed {
+            market: market.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Returns a sponsor's subsidy once the market is cancelled or resolved
+    /// to an option nobody bet on
+    pub fn withdraw_sponsorship(ctx: Context<WithdrawSponsorship>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_REFUNDS),
+            ErrorCode::RefundsPaused
+        );
+        let market = &mut ctx.accounts.market;
+        let sponsorship = &mut ctx.accounts.sponsorship;
+        require!(market.subsidy_refundable()?, ErrorCode::SubsidyNotRefundable);
+        
+        // Keep fees earned so far claimable
+        sponsorship.settle(market.sponsor_fee_per_share)?;
+        let amount = sponsorship.weight()?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            amount <= ctx.accounts.market_escrow.amount,
+            ErrorCode::InsufficientEscrowBalance
+        );
+        sponsorship.refunded = true;
+        market.subsidy_refunded = security_checks::safe_add(market.subsidy_refunded, amount)?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.market_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.sponsor_token_account.to_account_info(),
+                    authority: ctx.accounts.market_escrow.to_account_info(),
+                },
+                &[&[
+                    b"market_escrow",
+                    market.key().as_ref(),
+                    &[ctx.bumps.market_escrow],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("Sponsorship refunded for market: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Amount: {} tokens", amount);
+        
+        emit!(SponsorshipRefunded {
+            market: market.key(),
+            sponsor: ctx.accounts.sponsor.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn approve_market(ctx: Context<ApproveMarket>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_CREATION),
+            ErrorCode::CreationPaused
+        );
+        let market = &mut ctx.accounts.market;
+        market.status = MarketStatus::Active;
+        
+        msg!("Market approved: {}", String::from_utf8_lossy(&market.market_id));
+        
+        emit!(MarketApproved {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn reject_market(ctx: Context<RejectMarket>, slash_bond: bool) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_RESOLUTION),
+            ErrorCode::ResolutionPaused
+        );
+        let market = &mut ctx.accounts.market;
+        let bond = market.creator_bond;
+        
+        // Update state before moving the bond out of escrow
+        market.status = MarketStatus::Cancelled;
+        market.creator_bond = 0;
+        if slash_bond {
+            let bond_received = security_checks::safe_sub(
+                bond,
+                transfer_fee_for(&ctx.accounts.token_mint, bond)?
+            )?;
+            let fee_ledger = &mut ctx.accounts.fee_ledger;
+            fee_ledger.treasury_accrued = security_checks::safe_add(fee_ledger.treasury_accrued, bond_received)?;
         }
         
-        // Update total pool
-        market.total_pool = market.total_pool
-            .checked_add(net_amount)
-            .ok_or(ErrorCode::CalculationError)?;
+        let destination = if slash_bond {
+            ctx.accounts.treasury_vault.to_account_info()
+        } else {
+            ctx.accounts.creator_token_account.to_account_info()
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.market_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.market_escrow.to_account_info(),
+                },
+                &[&[
+                    b"market_escrow",
+                    market.key().as_ref(),
+                    &[ctx.bumps.market_escrow],
+                ]],
+            ),
+            bond,
+            ctx.accounts.token_mint.decimals,
+        )?;
         
-        // Ensure total pool doesn't exceed safety cap
+        msg!("Market rejected: {}", String::from_utf8_lossy(&market.market_id));
+        if slash_bond {
+            msg!("Bond slashed to treasury: {} tokens", bond);
+        } else {
+            msg!("Bond refunded to creator: {} tokens", bond);
+        }
+        
+        emit!(MarketRejected {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            bond_amount: bond,
+            bond_slashed: slash_bond,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Returns the creator's bond or whitelisted stake once the market is
+    /// resolved or cancelled
+    pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
         require!(
-            market.total_pool <= MAX_POOL_SIZE,
-            ErrorCode::PoolOverflow
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
         );
+        let market = &mut ctx.accounts.market;
+        let bond = security_checks::safe_add(market.creator_bond, market.creator_stake)?;
+        require!(bond > 0, ErrorCode::InvalidAmount);
         
-        // Now perform token transfers after all validations pass
-        token::transfer(
-            CpiContext::new(
+        market.creator_bond = 0;
+        market.creator_stake = 0;
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.user_token_account.to_account_info(),
-                    to: ctx.accounts.market_escrow.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.market_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.market_escrow.to_account_info(),
                 },
+                &[&[
+                    b"market_escrow",
+                    market.key().as_ref(),
+                    &[ctx.bumps.market_escrow],
+                ]],
             ),
-            net_amount,
+            bond,
+            ctx.accounts.token_mint.decimals,
         )?;
         
-        token::transfer(
+        msg!("Creator bond released: {} tokens", bond);
+        
+        emit!(CreatorBondReleased {
+            market: market.key(),
+            creator: ctx.accounts.creator.key(),
+            amount: bond,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn place_prediction(
+        ctx: Context<PlacePrediction>,
+        option_index: u8,
+        amount: u64,
+        burn_nonce: u64,
+    ) -> Result<()> {
+        place_bet(ctx.accounts, option_index, amount, burn_nonce)
+    }
+
+    /// Bets with a mint other than the market's collateral. `amount_in` of the
+    /// input mint is swapped through the pool pairing it with the collateral;
+    /// the whole transaction fails if fewer than `minimum_amount_out` collateral
+    /// tokens reach the user, and everything received is placed as the bet.
+    /// `burn_nonce` comes third so the nested PlacePrediction accounts read
+    /// the same instruction prefix as place_prediction.
+    pub fn place_prediction_with_swap(
+        ctx: Context<PlacePredictionWithSwap>,
+        option_index: u8,
+        amount_in: u64,
+        burn_nonce: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        let pool_state = &ctx.accounts.pool_state;
+        let input_mint = ctx.accounts.input_mint.key();
+        let collateral_mint = ctx.accounts.bet.token_mint.key();
+        
+        // The pool must pair the input mint with the market's collateral
+        let collateral_is_token_side = if pool_state.token_mint == collateral_mint
+            && pool_state.usdc_mint == input_mint {
+            true
+        } else if pool_state.usdc_mint == collateral_mint && pool_state.token_mint == input_mint {
+            false
+        } else {
+            return Err(ErrorCode::InvalidMint.into());
+        };
+        let (pool_input_vault, pool_output_vault, output_seed, output_bump) = if collateral_is_token_side {
+            (
+                &mut ctx.accounts.pool_usdc_account,
+                &mut ctx.accounts.pool_token_account,
+                b"pool_token_account".as_ref(),
+                ctx.bumps.pool_token_account,
+            )
+        } else {
+            (
+                &mut ctx.accounts.pool_token_account,
+                &mut ctx.accounts.pool_usdc_account,
+                b"pool_usdc_account".as_ref(),
+                ctx.bumps.pool_usdc_account,
+            )
+        };
+        
+        let input_before = pool_input_vault.amount;
+        token_interface::transfer_checked(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.user_token_account.to_account_info(),
-                    to: ctx.accounts.burn_token_account.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                ctx.accounts.input_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_input_account.to_account_info(),
+                    mint: ctx.accounts.input_mint.to_account_info(),
+                    to: pool_input_vault.to_account_info(),
+                    authority: ctx.accounts.bet.user.to_account_info(),
                 },
             ),
-            burn_amount,
+            amount_in,
+            ctx.accounts.input_mint.decimals,
         )?;
+        pool_input_vault.reload()?;
         
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.user_token_account.to_account_info(),
-                    to: ctx.accounts.treasury_token_account.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+        // Reserves only grow by what the pool actually received, net of any
+        // input mint transfer fee
+        let input_received = security_checks::safe_sub(pool_input_vault.amount, input_before)?;
+        let amount_out = ctx.accounts.pool_state.apply_swap(!collateral_is_token_side, input_received)?;
+        
+        let pool_key = ctx.accounts.pool_state.key();
+        let user_token_account = &mut ctx.accounts.bet.user_token_account;
+        let collateral_before = user_token_account.amount;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.bet.token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_output_vault.to_account_info(),
+                    mint: ctx.accounts.bet.token_mint.to_account_info(),
+                    to: user_token_account.to_account_info(),
+                    authority: pool_output_vault.to_account_info(),
                 },
+                &[&[output_seed, pool_key.as_ref(), &[output_bump]]],
             ),
-            platform_fee,
+            amount_out,
+            ctx.accounts.bet.token_mint.decimals,
         )?;
+        user_token_account.reload()?;
         
-        let prediction = &mut ctx.accounts.prediction;
+        // Only what reaches the user's collateral account is bet
+        let bet_amount = security_checks::safe_sub(user_token_account.amount, collateral_before)?;
+        require!(bet_amount >= minimum_amount_out, ErrorCode::SlippageTooHigh);
         
-        // Check if this is an existing prediction
-        if prediction.user != Pubkey::default() {
-            // Existing prediction - just add to the amount
-            prediction.amount = prediction.amount
-                .checked_add(net_amount)
-                .ok_or(ErrorCode::CalculationError)?;
-            
-            msg!("Added to existing prediction on market: {}", String::from_utf8_lossy(&market.market_id));
-            msg!("Option: {}", option_index);
-            msg!("Previous amount: {} tokens", prediction.amount.saturating_sub(net_amount));
-            msg!("Additional amount: {} tokens", net_amount);
-            msg!("New total amount: {} tokens", prediction.amount);
-        } else {
-            // New prediction
-            prediction.user = ctx.accounts.user.key();
-            prediction.option_index = option_index;
-            prediction.amount = net_amount;
-            prediction.timestamp = Clock::get()?.unix_timestamp;
-            prediction.claimed = false;
-            
-            msg!("New prediction placed on market: {}", String::from_utf8_lossy(&market.market_id));
-            msg!("Option index: {}", option_index);
-            msg!("Amount: {} tokens", amount);
-            msg!("Net amount: {} tokens", net_amount);
-        }
+        msg!("Swapped {} input tokens for {} collateral tokens", amount_in, amount_out);
         
-        msg!("Burn amount: {} tokens", burn_amount);
-        msg!("Platform fee: {} tokens", platform_fee);
-        Ok(())
+        emit!(SwapBetPlaced {
+            market: ctx.accounts.bet.market.key(),
+            user: ctx.accounts.bet.user.key(),
+            pool: pool_key,
+            input_mint,
+            amount_in,
+            amount_out,
+            bet_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        place_bet(&mut ctx.accounts.bet, option_index, bet_amount, burn_nonce)
+    }
+
+    pub fn initialize_liquidity_pool(
+        ctx: Context<InitializeLiquidityPool>,
+        pool_id: String,
+        swap_fee_bps: u16,
+    ) -> Result<()> {
+        meteora::meteora_integration::initialize_liquidity_pool(ctx, pool_id, swap_fee_bps)
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<ManageLiquidity>,
+        max_token_amount: u64,
+        max_usdc_amount: u64,
+        minimum_lp_amount: u64,
+    ) -> Result<()> {
+        meteora::meteora_integration::add_liquidity(ctx, max_token_amount, max_usdc_amount, minimum_lp_amount)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<ManageLiquidity>,
+        lp_amount: u64,
+        minimum_token_amount: u64,
+        minimum_usdc_amount: u64,
+    ) -> Result<()> {
+        meteora::meteora_integration::remove_liquidity(ctx, lp_amount, minimum_token_amount, minimum_usdc_amount)
+    }
+
+    pub fn swap_tokens(
+        ctx: Context<SwapTokens>,
+        input_amount: u64,
+        minimum_output_amount: u64,
+        is_token_to_usdc: bool,
+    ) -> Result<()> {
+        meteora::meteora_integration::swap_tokens(ctx, input_amount, minimum_output_amount, is_token_to_usdc)
+    }
+
+    pub fn set_pool_active(ctx: Context<SetPoolActive>, is_active: bool) -> Result<()> {
+        meteora::meteora_integration::set_pool_active(ctx, is_active)
     }
 
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
         winning_option: u8,
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_RESOLUTION),
+            ErrorCode::ResolutionPaused
+        );
         let market = &mut ctx.accounts.market;
         
         // CRITICAL: Add authority check
@@ -733,11 +3309,8 @@ pub mod market_system {
             ErrorCode::Unauthorized
         );
         
-        // Validate market status
-        require!(
-            market.status == MarketStatus::Active,
-            ErrorCode::MarketNotActive
-        );
+        // Validate market status (paused markets can still be settled)
+        require!(market.is_live(), ErrorCode::MarketNotActive);
         
         // Validate winning option
         security_checks::validate_option_index(winning_option, market.option_count)?;
@@ -745,17 +3318,31 @@ pub mod market_system {
         // Update market status
         market.status = MarketStatus::Resolved;
         market.winning_option = Some(winning_option);
+        release_creator_quota(market, ctx.accounts.creator_config.as_mut())?;
         
         msg!("Market resolved with winning option: {}", winning_option);
+        
+        emit!(MarketResolved {
+            market: market.key(),
+            resolver: ctx.accounts.resolver.key(),
+            winning_option,
+            winning_pool: market.option_pool(winning_option)?,
+            total_pool: market.total_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
-    pub fn claim_reward(ctx: Context<ClaimReward>, option_index: u8) -> Result<()> {
-        let market = &ctx.accounts.market;
+    pub fn claim_reward(ctx: Context<ClaimReward>, option_index: u8, burn_nonce: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
         let prediction = &mut ctx.accounts.prediction;
         let platform_state = &ctx.accounts.platform_state;
         
         // Security validations
+        require!(
+            !platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
+        );
         require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
         require!(!prediction.claimed, ErrorCode::AlreadyClaimed);
         
@@ -768,25 +3355,19 @@ pub mod market_system {
         let is_winner = option_index == winning_option;
         require!(is_winner, ErrorCode::NotWinner);
         
-        // Get the winning pool based on winning option with bounds check
-        let winning_pool = match winning_option {
-            0 => market.option_1_pool,
-            1 => market.option_2_pool,
-            2 => market.option_3_pool,
-            3 => market.option_4_pool,
-            _ => return Err(ErrorCode::InvalidOptionIndex.into()),
-        };
+        // Winners' stakes, without sponsor seed, share the whole payout pool
+        let winning_pool = market.option_stake(winning_option)?;
         
         // Ensure no division by zero
         require!(winning_pool > 0, ErrorCode::DivisionByZero);
         require!(prediction.amount > 0, ErrorCode::InvalidAmount);
         
         // Calculate reward using u128 to prevent overflow
-        let total_pool = market.total_pool as u128;
+        let total_pool = market.payout_pool()? as u128;
         let user_amount = prediction.amount as u128;
         let win_pool = winning_pool as u128;
         
-        // Calculate: (total_pool * user_amount) / winning_pool
+        // Calculate: (payout_pool * user_amount) / winning_stake
         let reward_amount_u128 = total_pool
             .checked_mul(user_amount)
             .ok_or(ErrorCode::CalculationError)?
@@ -804,7 +3385,10 @@ pub mod market_system {
         )?;
         
         let burn_amount = security_checks::safe_div(
-            security_checks::safe_mul(reward_amount, platform_state.claim_burn_rate)?,
+            security_checks::safe_mul(
+                reward_amount,
+                ctx.accounts.collateral_config.burn_rate(platform_state.claim_burn_rate)
+            )?,
             10000
         )?;
         
@@ -820,11 +3404,26 @@ pub mod market_system {
             ErrorCode::InsufficientEscrowBalance
         );
         
-        token::transfer(
+        market.total_claimed = market.total_claimed
+            .checked_add(reward_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        market.fees_collected = market.fees_collected
+            .checked_add(creator_fee)
+            .ok_or(ErrorCode::CalculationError)?;
+        let creator_fee_received = security_checks::safe_sub(
+            creator_fee,
+            transfer_fee_for(&ctx.accounts.token_mint, creator_fee)?
+        )?;
+        let fee_shares = FeeShares::split(creator_fee_received, &platform_state.fee_splits, FeeSource::Claim)?;
+        ctx.accounts.fee_ledger.accrue(market, None, &fee_shares)?;
+        ctx.accounts.collateral_config.record_activity(0, burn_amount)?;
+        
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.market_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.user_token_account.to_account_info(),
                     authority: ctx.accounts.market_escrow.to_account_info(),
                 },
@@ -835,13 +3434,15 @@ pub mod market_system {
                 ]],
             ),
             net_reward,
+            ctx.accounts.token_mint.decimals,
         )?;
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.market_escrow.to_account_info(),
-                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.treasury_vault.to_account_info(),
                     authority: ctx.accounts.market_escrow.to_account_info(),
                 },
                 &[&[
@@ -851,13 +3452,14 @@ pub mod market_system {
                 ]],
             ),
             creator_fee,
+            ctx.accounts.token_mint.decimals,
         )?;
-        token::transfer(
+        token_interface::burn(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     from: ctx.accounts.market_escrow.to_account_info(),
-                    to: ctx.accounts.burn_token_account.to_account_info(),
                     authority: ctx.accounts.market_escrow.to_account_info(),
                 },
                 &[&[
@@ -877,10 +3479,48 @@ pub mod market_system {
         msg!("Creator fee: {} tokens", creator_fee);
         msg!("Burn amount: {} tokens", burn_amount);
         msg!("Net reward: {} tokens", net_reward);
+        
+        if let Some(sequence) = record_burn(
+            &mut ctx.accounts.collateral_config,
+            ctx.accounts.burn_receipt.as_mut(),
+            BurnType::RewardClaim,
+            ctx.accounts.user.key(),
+            market.key(),
+            burn_nonce,
+            reward_amount,
+            burn_amount,
+        )? {
+            believeapp_integration::process_reward_burn(
+                sequence,
+                market.token_mint,
+                ctx.accounts.user.key(),
+                market.key(),
+                reward_amount,
+                burn_amount,
+            )?;
+        }
+        
+        emit!(RewardClaimed {
+            market: market.key(),
+            user: ctx.accounts.user.key(),
+            option_index,
+            reward_amount,
+            creator_fee,
+            burn_amount,
+            net_reward,
+            winning_pool,
+            total_pool: market.total_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        emit_fees_accrued(market.key(), FeeSource::Claim, creator_fee_received, &fee_shares)?;
         Ok(())
     }
 
     pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_RESOLUTION),
+            ErrorCode::ResolutionPaused
+        );
         let market = &mut ctx.accounts.market;
         
         // Authority check is already in the account constraint, but let's be explicit
@@ -889,22 +3529,64 @@ pub mod market_system {
             ErrorCode::Unauthorized
         );
         
-        require!(
-            market.status == MarketStatus::Active,
-            ErrorCode::MarketNotActive
-        );
+        require!(market.is_live(), ErrorCode::MarketNotActive);
         
         market.status = MarketStatus::Cancelled;
+        release_creator_quota(market, ctx.accounts.creator_config.as_mut())?;
         
         msg!("Market cancelled by authority");
+        
+        emit!(MarketCancelled {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            total_pool: market.total_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stops new predictions on a single market. Nothing can be claimed or
+    /// refunded while paused; the authority can still resolve or cancel the
+    /// market, after which claim_reward / emergency_withdraw work as usual.
+    pub fn pause_market(ctx: Context<SetMarketPause>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
+        
+        market.status = MarketStatus::Paused;
+        
+        emit!(MarketPaused {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Market paused: {}", String::from_utf8_lossy(&market.market_id));
+        Ok(())
+    }
+
+    pub fn unpause_market(ctx: Context<SetMarketPause>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Paused, ErrorCode::MarketNotPaused);
+        
+        market.status = MarketStatus::Active;
+        
+        emit!(MarketUnpaused {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Market unpaused: {}", String::from_utf8_lossy(&market.market_id));
         Ok(())
     }
 
     pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, option_index: u8) -> Result<()> {
-        let market = &ctx.accounts.market;
+        let market = &mut ctx.accounts.market;
         let prediction = &mut ctx.accounts.prediction;
         
         // Security validations
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_REFUNDS),
+            ErrorCode::RefundsPaused
+        );
         require!(market.status == MarketStatus::Cancelled, ErrorCode::MarketNotCancelled);
         require!(!prediction.claimed, ErrorCode::AlreadyClaimed);
         
@@ -921,12 +3603,17 @@ pub mod market_system {
             ErrorCode::InsufficientEscrowBalance
         );
         
+        market.total_refunded = market.total_refunded
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        
         // Transfer the original bet amount back to the user
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.market_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.user_token_account.to_account_info(),
                     authority: ctx.accounts.market_escrow.to_account_info(),
                 },
@@ -937,6 +3624,7 @@ pub mod market_system {
                 ]],
             ),
             refund_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Mark as claimed to prevent double withdrawal
@@ -946,10 +3634,871 @@ pub mod market_system {
         msg!("User: {}", prediction.user);
         msg!("Refund amount: {} tokens", refund_amount);
         
+        emit!(PredictionRefunded {
+            market: market.key(),
+            user: ctx.accounts.user.key(),
+            option_index,
+            amount: refund_amount,
+            total_refunded: market.total_refunded,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn initialize_parlay_house(
+        ctx: Context<InitializeParlayHouse>,
+        edge_bps: u16,
+        max_multiplier_bps: u64,
+        max_leg_odds_bps: u64,
+        min_leg_pool_tokens: u64,
+        min_leg_bettors: u32,
+    ) -> Result<()> {
+        require!(
+            edge_bps <= ParlayHouse::MAX_EDGE_BPS && max_multiplier_bps > 10000 && max_leg_odds_bps > 10000,
+            ErrorCode::InvalidParlayConfig
+        );
+        let parlay_house = &mut ctx.accounts.parlay_house;
+        parlay_house.mint = ctx.accounts.token_mint.key();
+        parlay_house.edge_bps = edge_bps;
+        parlay_house.max_multiplier_bps = max_multiplier_bps;
+        parlay_house.max_leg_odds_bps = max_leg_odds_bps;
+        parlay_house.min_leg_pool_tokens = min_leg_pool_tokens;
+        parlay_house.min_leg_bettors = min_leg_bettors;
+        
+        msg!("Parlay house initialized for mint: {}", parlay_house.mint);
+        msg!("House edge: {}bp, max multiplier: {}bp", edge_bps, max_multiplier_bps);
+        msg!("Max leg odds: {}bp, min leg pool: {} whole tokens", max_leg_odds_bps, min_leg_pool_tokens);
+        msg!("Min leg bettors: {}", min_leg_bettors);
+        
+        emit!(ParlayHouseUpdated {
+            authority: ctx.accounts.authority.key(),
+            mint: parlay_house.mint,
+            edge_bps,
+            max_multiplier_bps,
+            max_leg_odds_bps,
+            min_leg_pool_tokens,
+            min_leg_bettors,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Changes the odds terms for new parlays; open parlays keep theirs
+    pub fn set_parlay_house_config(
+        ctx: Context<UpdateParlayHouse>,
+        edge_bps: u16,
+        max_multiplier_bps: u64,
+        max_leg_odds_bps: u64,
+        min_leg_pool_tokens: u64,
+        min_leg_bettors: u32,
+    ) -> Result<()> {
+        require!(
+            edge_bps <= ParlayHouse::MAX_EDGE_BPS && max_multiplier_bps > 10000 && max_leg_odds_bps > 10000,
+            ErrorCode::InvalidParlayConfig
+        );
+        let parlay_house = &mut ctx.accounts.parlay_house;
+        parlay_house.edge_bps = edge_bps;
+        parlay_house.max_multiplier_bps = max_multiplier_bps;
+        parlay_house.max_leg_odds_bps = max_leg_odds_bps;
+        parlay_house.min_leg_pool_tokens = min_leg_pool_tokens;
+        parlay_house.min_leg_bettors = min_leg_bettors;
+        
+        msg!("House edge: {}bp, max multiplier: {}bp", edge_bps, max_multiplier_bps);
+        msg!("Max leg odds: {}bp, min leg pool: {} whole tokens", max_leg_odds_bps, min_leg_pool_tokens);
+        msg!("Min leg bettors: {}", min_leg_bettors);
+        
+        emit!(ParlayHouseUpdated {
+            authority: ctx.accounts.authority.key(),
+            mint: parlay_house.mint,
+            edge_bps,
+            max_multiplier_bps,
+            max_leg_odds_bps,
+            min_leg_pool_tokens,
+            min_leg_bettors,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Adds house liquidity backing parlay payouts
+    pub fn fund_parlay_house(ctx: Context<ManageParlayLiquidity>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let received = security_checks::safe_sub(
+            amount,
+            transfer_fee_for(&ctx.accounts.token_mint, amount)?
+        )?;
+        let parlay_house = &mut ctx.accounts.parlay_house;
+        parlay_house.balance = security_checks::safe_add(parlay_house.balance, received)?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.parlay_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("Parlay house funded: {} tokens", received);
+        
+        emit!(ParlayLiquidityUpdated {
+            authority: ctx.accounts.authority.key(),
+            mint: parlay_house.mint,
+            deposited: received,
+            withdrawn: 0,
+            balance: parlay_house.balance,
+            reserved: parlay_house.reserved,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Withdraws house liquidity not reserved for open parlays
+    pub fn withdraw_parlay_house(ctx: Context<ManageParlayLiquidity>, amount: u64) -> Result<()> {
+        let parlay_house = &mut ctx.accounts.parlay_house;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= parlay_house.available()?, ErrorCode::InsufficientHouseLiquidity);
+        parlay_house.balance -= amount;
+        
+        let mint = parlay_house.mint;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.parlay_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.authority_token_account.to_account_info(),
+                    authority: ctx.accounts.parlay_vault.to_account_info(),
+                },
+                &[&[
+                    b"parlay_vault",
+                    mint.as_ref(),
+                    &[ctx.bumps.parlay_vault],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("Parlay house withdrawal: {} tokens", amount);
+        
+        emit!(ParlayLiquidityUpdated {
+            authority: ctx.accounts.authority.key(),
+            mint,
+            deposited: 0,
+            withdrawn: amount,
+            balance: parlay_house.balance,
+            reserved: parlay_house.reserved,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stakes once on every leg winning. Each leg's odds are locked at the
+    /// market's parimutuel multiple net of claim fees, ignoring pool changes
+    /// made in the current slot and capped by the house; the combined
+    /// payout, less the house edge and capped by the house, must be at least
+    /// `minimum_payout`.
+    pub fn place_parlay(
+        ctx: Context<PlaceParlay>,
+        parlay_id: u64,
+        stake: u64,
+        option_indexes: Vec<u8>,
+        minimum_payout: u64,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_BETTING),
+            ErrorCode::BettingPaused
+        );
+        require!(stake > 0, ErrorCode::InvalidAmount);
+        require!(
+            option_indexes.len() >= Parlay::MIN_LEGS
+                && option_indexes.len() <= Parlay::MAX_LEGS
+                && option_indexes.len() == ctx.remaining_accounts.len(),
+            ErrorCode::InvalidParlayLegs
+        );
+        
+        let parlay_house = &mut ctx.accounts.parlay_house;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        let decimals = ctx.accounts.token_mint.decimals;
+        let claim_burn_rate = ctx.accounts.collateral_config.burn_rate(ctx.accounts.platform_state.claim_burn_rate);
+        let mut latest_resolution = current_time;
+        let mut legs: Vec<ParlayLeg> = Vec::with_capacity(option_indexes.len());
+        for (market_info, option_index) in ctx.remaining_accounts.iter().zip(option_indexes) {
+            require!(
+                !legs.iter().any(|leg| leg.market == market_info.key()),
+                ErrorCode::InvalidParlayLegs
+            );
+            let market = load_market(market_info)?;
+            require!(market.token_mint == parlay_house.mint, ErrorCode::InvalidMint);
+            require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
+            require!(current_time < market.resolution_date, ErrorCode::MarketClosed);
+            latest_resolution = latest_resolution.max(market.resolution_date);
+            legs.push(ParlayLeg {
+                market: market_info.key(),
+                option_index,
+                odds_bps: parlay_house.leg_odds(&market, option_index, decimals, claim_burn_rate, clock.slot)?,
+                result: LegResult::Pending,
+            });
+        }
+        
+        // Only what reaches the vault is staked
+        let net_stake = security_checks::safe_sub(
+            stake,
+            transfer_fee_for(&ctx.accounts.token_mint, stake)?
+        )?;
+        let payout_cap = security_checks::safe_div(
+            security_checks::safe_mul(net_stake, parlay_house.max_multiplier_bps)?,
+            10000
+        )?;
+        let payout = Parlay::payout_for(net_stake, &legs, parlay_house.edge_bps, payout_cap)?;
+        require!(payout >= minimum_payout, ErrorCode::SlippageTooHigh);
+        parlay_house.book(net_stake, payout)?;
+        
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.parlay_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            stake,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        let parlay = &mut ctx.accounts.parlay;
+        parlay.user = ctx.accounts.user.key();
+        parlay.mint = parlay_house.mint;
+        parlay.parlay_id = parlay_id;
+        parlay.stake = net_stake;
+        parlay.payout = payout;
+        parlay.edge_bps = parlay_house.edge_bps;
+        parlay.legs = legs;
+        parlay.placed_at = current_time;
+        parlay.settled = false;
+        parlay.paid_out = 0;
+        parlay.expires_at = latest_resolution
+            .checked_add(Parlay::EXPIRY_GRACE)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        msg!("Parlay placed: {} legs", parlay.legs.len());
+        msg!("Stake: {} tokens, payout if all legs win: {} tokens", net_stake, payout);
+        
+        emit!(ParlayPlaced {
+            parlay: parlay.key(),
+            user: parlay.user,
+            mint: parlay.mint,
+            stake: net_stake,
+            payout,
+            legs: parlay.legs.clone(),
+            expires_at: parlay.expires_at,
+            timestamp: current_time,
+        });
+        Ok(())
+    }
+
+    /// Settles a parlay once every leg's market is resolved or cancelled, or
+    /// once it has expired. Cancelled legs, and legs still open at expiry, are
+    /// voided and the payout recomputed from the rest; if every leg is void
+    /// the stake is returned.
+    pub fn settle_parlay(ctx: Context<SettleParlay>) -> Result<()> {
+        require!(
+            !ctx.accounts.platform_state.is_paused(PlatformState::PAUSE_PAYOUTS),
+            ErrorCode::PayoutsPaused
+        );
+        let parlay = &mut ctx.accounts.parlay;
+        require!(
+            parlay.legs.len() == ctx.remaining_accounts.len(),
+            ErrorCode::InvalidParlayLegs
+        );
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut results = Vec::with_capacity(parlay.legs.len());
+        for (leg, market_info) in parlay.legs.iter().zip(ctx.remaining_accounts.iter()) {
+            require!(leg.market == market_info.key(), ErrorCode::InvalidParlayLegs);
+            let market = load_market(market_info)?;
+            results.push(parlay.leg_result(leg, &market, current_time)?);
+        }
+        for (leg, result) in parlay.legs.iter_mut().zip(results) {
+            leg.result = result;
+        }
+        
+        let won = !parlay.legs.iter().any(|leg| leg.result == LegResult::Lost);
+        let paid_out = if won {
+            Parlay::payout_for(parlay.stake, &parlay.legs, parlay.edge_bps, parlay.payout)?
+        } else {
+            0
+        };
+        ctx.accounts.parlay_house.settle(parlay.payout, paid_out)?;
+        parlay.settled = true;
+        parlay.paid_out = paid_out;
+        
+        if paid_out > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.parlay_vault.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        authority: ctx.accounts.parlay_vault.to_account_info(),
+                    },
+                    &[&[
+                        b"parlay_vault",
+                        parlay.mint.as_ref(),
+                        &[ctx.bumps.parlay_vault],
+                    ]],
+                ),
+                paid_out,
+                ctx.accounts.token_mint.decimals,
+            )?;
+        }
+        
+        let voided_legs = parlay.legs.iter().filter(|leg| leg.result == LegResult::Void).count() as u8;
+        msg!("Parlay settled: {}", if won { "won" } else { "lost" });
+        msg!("Paid out: {} tokens", paid_out);
+        
+        emit!(ParlaySettled {
+            parlay: parlay.key(),
+            user: parlay.user,
+            won,
+            voided_legs,
+            paid_out,
+            timestamp: current_time,
+        });
+        Ok(())
+    }
+
+    /// Closes a settled parlay and returns its rent to the user
+    pub fn close_parlay(ctx: Context<CloseParlay>) -> Result<()> {
+        let parlay = &ctx.accounts.parlay;
+        
+        msg!("Parlay closed: {}", parlay.key());
+        
+        emit!(ParlayClosed {
+            parlay: parlay.key(),
+            user: parlay.user,
+            paid_out: parlay.paid_out,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Read-only view of what the escrow owes versus what it holds
+    pub fn escrow_reconciliation(ctx: Context<EscrowReconciliation>) -> Result<EscrowBalance> {
+        let owed = ctx.accounts.market.escrow_liability()?;
+        let held = ctx.accounts.market_escrow.amount;
+        
+        Ok(EscrowBalance {
+            owed,
+            held,
+            surplus: held.saturating_sub(owed),
+        })
+    }
+
+    /// Moves tokens the market does not owe out of escrow. From the escrow
+    /// itself only the surplus over escrow_liability is withdrawable; any
+    /// other token account owned by the escrow PDA (e.g. a wrong-mint ATA)
+    /// holds nothing the market owes. Recovered tokens are credited to the
+    /// treasury of their mint, whose vault must already exist.
+    pub fn recover_escrow_surplus(ctx: Context<RecoverEscrowSurplus>, amount: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let source = &ctx.accounts.source_token_account;
+        
+        let recoverable = if source.key() == ctx.accounts.market_escrow.key() {
+            source.amount.saturating_sub(market.escrow_liability()?)
+        } else {
+            source.amount
+        };
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= recoverable, ErrorCode::InsufficientSurplus);
+        
+        let destination_before = ctx.accounts.destination_token_account.amount;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: source.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.market_escrow.to_account_info(),
+                },
+                &[&[
+                    b"market_escrow",
+                    market.key().as_ref(),
+                    &[ctx.bumps.market_escrow],
+                ]],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        ctx.accounts.destination_token_account.reload()?;
+        
+        // Net of any transfer fee, the surplus becomes withdrawable treasury funds
+        let received = security_checks::safe_sub(
+            ctx.accounts.destination_token_account.amount,
+            destination_before
+        )?;
+        let fee_ledger = &mut ctx.accounts.fee_ledger;
+        fee_ledger.treasury_accrued = security_checks::safe_add(fee_ledger.treasury_accrued, received)?;
+        
+        msg!("Escrow surplus recovered: {} tokens", amount);
+        msg!("Mint: {}", source.mint);
+        msg!("Destination: {}", ctx.accounts.destination_token_account.key());
+        
+        emit!(EscrowSurplusRecovered {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            source: source.key(),
+            destination: ctx.accounts.destination_token_account.key(),
+            mint: source.mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Rewrites the platform state from the first deployment's layout into
+    /// the current one, with the settings added since at their defaults
+    pub fn migrate_platform_state(ctx: Context<MigratePlatformState>) -> Result<()> {
+        let account = ctx.accounts.platform_state.to_account_info();
+        let data = migration::legacy_data::<PlatformState>(&account, LegacyPlatformState::LEN)?;
+        let legacy = LegacyPlatformState::deserialize(&mut &data[..])?;
+        require!(legacy.authority == ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        
+        migration::rewrite(
+            &account,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + PlatformState::LEN,
+            &legacy.migrate(),
+        )?;
+        
+        msg!("Platform state migrated");
+        
+        emit!(PlatformStateMigrated {
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Rewrites a market from the first deployment's layout into the
+    /// current one. What a resolved or cancelled market already paid out is
+    /// taken from its escrow balance; the other fields added since start at zero.
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        let account = ctx.accounts.market.to_account_info();
+        let data = migration::legacy_data::<Market>(&account, LegacyMarket::LEN)?;
+        let legacy = LegacyMarket::deserialize(&mut &data[..])?;
+        require!(legacy.token_mint == ctx.accounts.market_escrow.mint, ErrorCode::InvalidMint);
+        let market = legacy.migrate(ctx.accounts.market_escrow.amount);
+        
+        migration::rewrite(
+            &account,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Market::LEN,
+            &market,
+        )?;
+        
+        msg!("Market migrated: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Paid out before migration: {} claimed, {} refunded", market.total_claimed, market.total_refunded);
+        
+        emit!(MarketMigrated {
+            market: account.key(),
+            authority: ctx.accounts.authority.key(),
+            total_claimed: market.total_claimed,
+            total_refunded: market.total_refunded,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 }
 
+/// Shared body of place_prediction and place_prediction_with_swap: charges
+/// fees and burns on `amount` of the user's collateral and books the bet.
+fn place_bet(accounts: &mut PlacePrediction, option_index: u8, amount: u64, burn_nonce: u64) -> Result<()> {
+    let market = &mut accounts.market;
+    let platform_state = &accounts.platform_state;
+    
+    // Security validations
+    require!(
+        !platform_state.is_paused(PlatformState::PAUSE_BETTING),
+        ErrorCode::BettingPaused
+    );
+    require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
+    require!(
+        Clock::get()?.unix_timestamp < market.resolution_date,
+        ErrorCode::MarketClosed
+    );
+    
+    // Validate option index
+    security_checks::validate_option_index(option_index, market.option_count)?;
+    
+    // Validate bet amount against the market and collateral limits
+    let decimals = accounts.token_mint.decimals;
+    let collateral_config = &mut accounts.collateral_config;
+    security_checks::validate_bet_amount(
+        amount,
+        market.min_bet_amount.max(collateral_config.min_bet_amount(decimals)?),
+        collateral_config.max_bet_amount(decimals)?
+    )?;
+    
+    // Calculate fees using safe math, at the user's volume tier in this mint
+    let user_stats = &mut accounts.user_stats;
+    let volume_tokens = security_checks::safe_div(
+        user_stats.total_volume,
+        security_checks::scale_to_mint_units(1, decimals)?
+    )?;
+    let points_discount_bps = accounts.pmp_token_account
+        .as_ref()
+        .map(|points| platform_state.points_discount_for(points.amount))
+        .unwrap_or(0);
+    let (platform_fee_rate, bet_burn_rate) =
+        platform_state.bet_rates(volume_tokens, collateral_config, points_discount_bps)?;
+    let burn_amount = security_checks::safe_div(
+        security_checks::safe_mul(amount, bet_burn_rate)?,
+        10000
+    )?;
+    
+    let platform_fee = security_checks::safe_div(
+        security_checks::safe_mul(amount, platform_fee_rate)?,
+        10000
+    )?;
+    
+    let escrow_transfer = security_checks::safe_sub(
+        security_checks::safe_sub(amount, burn_amount)?,
+        platform_fee
+    )?;
+    
+    // Transfer-fee mints withhold part of each transfer; only what
+    // actually arrives in the escrow and vault is credited
+    let escrow_transfer_fee = transfer_fee_for(&accounts.token_mint, escrow_transfer)?;
+    let platform_fee_transfer_fee = transfer_fee_for(&accounts.token_mint, platform_fee)?;
+    let net_amount = security_checks::safe_sub(escrow_transfer, escrow_transfer_fee)?;
+    let platform_fee_received = security_checks::safe_sub(platform_fee, platform_fee_transfer_fee)?;
+    let transfer_fee = security_checks::safe_add(escrow_transfer_fee, platform_fee_transfer_fee)?;
+    
+    // Validate pool addition before any transfers
+    security_checks::validate_pool_addition(
+        market.total_pool,
+        net_amount,
+        collateral_config.max_pool_amount(decimals)?
+    )?;
+    
+    // Update the correct option pool based on option_index
+    market.record_pool_change(Clock::get()?.slot);
+    match option_index {
+        0 => {
+            market.option_1_pool = market.option_1_pool
+                .checked_add(net_amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        },
+        1 => {
+            market.option_2_pool = market.option_2_pool
+                .checked_add(net_amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        },
+        2 => {
+            market.option_3_pool = market.option_3_pool
+                .checked_add(net_amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        },
+        3 => {
+            market.option_4_pool = market.option_4_pool
+                .checked_add(net_amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        },
+        _ => return Err(ErrorCode::InvalidOptionIndex.into()),
+    }
+    
+    // Update total pool
+    market.total_pool = market.total_pool
+        .checked_add(net_amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    
+    // Enforce per-user exposure caps on the accumulated position
+    let position_amount = security_checks::safe_add(accounts.prediction.amount, net_amount)?;
+    market.validate_exposure(option_index, position_amount, net_amount)?;
+    
+    // Update market and platform stats
+    market.volume = market.volume
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    market.fees_collected = market.fees_collected
+        .checked_add(platform_fee)
+        .ok_or(ErrorCode::CalculationError)?;
+    let fee_shares = FeeShares::split(platform_fee_received, &platform_state.fee_splits, FeeSource::Bet)?;
+    let referrer_rewards = bind_referral(
+        &mut accounts.referral,
+        accounts.referrer_rewards.as_mut(),
+        user_stats,
+        accounts.user.key(),
+    )?;
+    accounts.fee_ledger.accrue(market, referrer_rewards, &fee_shares)?;
+    collateral_config.record_activity(amount, burn_amount)?;
+    user_stats.user = accounts.user.key();
+    user_stats.mint = market.token_mint;
+    user_stats.total_volume = user_stats.total_volume
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    user_stats.bet_count = user_stats.bet_count
+        .checked_add(1)
+        .ok_or(ErrorCode::CalculationError)?;
+    user_stats.last_bet_at = Clock::get()?.unix_timestamp;
+    
+    // Now perform token transfers after all validations pass
+    token_interface::transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_token_account.to_account_info(),
+                mint: accounts.token_mint.to_account_info(),
+                to: accounts.market_escrow.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        escrow_transfer,
+        accounts.token_mint.decimals,
+    )?;
+    
+    token_interface::burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.token_mint.to_account_info(),
+                from: accounts.user_token_account.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        burn_amount,
+    )?;
+    
+    token_interface::transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_token_account.to_account_info(),
+                mint: accounts.token_mint.to_account_info(),
+                to: accounts.treasury_vault.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        platform_fee,
+        accounts.token_mint.decimals,
+    )?;
+    
+    let prediction = &mut accounts.prediction;
+    
+    // Check if this is an existing prediction
+    if prediction.user != Pubkey::default() {
+        // Existing prediction - just add to the amount
+        prediction.amount = prediction.amount
+            .checked_add(net_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        msg!("Added to existing prediction on market: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Option: {}", option_index);
+        msg!("Previous amount: {} tokens", prediction.amount.saturating_sub(net_amount));
+        msg!("Additional amount: {} tokens", net_amount);
+        msg!("New total amount: {} tokens", prediction.amount);
+    } else {
+        // New prediction
+        prediction.user = accounts.user.key();
+        prediction.option_index = option_index;
+        prediction.amount = net_amount;
+        prediction.timestamp = Clock::get()?.unix_timestamp;
+        prediction.claimed = false;
+        market.bettor_count = market.bettor_count
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        msg!("New prediction placed on market: {}", String::from_utf8_lossy(&market.market_id));
+        msg!("Option index: {}", option_index);
+        msg!("Amount: {} tokens", amount);
+        msg!("Net amount: {} tokens", net_amount);
+    }
+    
+    msg!("Burn amount: {} tokens ({}bp)", burn_amount, bet_burn_rate);
+    msg!("Platform fee: {} tokens ({}bp)", platform_fee, platform_fee_rate);
+    if transfer_fee > 0 {
+        msg!("Transfer fee withheld: {} tokens", transfer_fee);
+    }
+    
+    if let Some(sequence) = record_burn(
+        collateral_config,
+        accounts.burn_receipt.as_mut(),
+        BurnType::PredictionBet,
+        accounts.user.key(),
+        market.key(),
+        burn_nonce,
+        amount,
+        burn_amount,
+    )? {
+        believeapp_integration::process_bet_burn(
+            sequence,
+            market.token_mint,
+            accounts.user.key(),
+            market.key(),
+            market.option_name(option_index)?,
+            amount,
+            burn_amount,
+        )?;
+    }
+    
+    emit!(PredictionPlaced {
+        market: market.key(),
+        user: accounts.user.key(),
+        option_index,
+        amount,
+        net_amount,
+        burn_amount,
+        platform_fee,
+        bet_burn_rate,
+        platform_fee_rate,
+        points_discount_bps,
+        transfer_fee,
+        position_amount: prediction.amount,
+        option_pool: market.option_pool(option_index)?,
+        total_pool: market.total_pool,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    emit_fees_accrued(market.key(), FeeSource::Bet, platform_fee_received, &fee_shares)?;
+    Ok(())
+}
+
+/// Assigns the mint's next burn sequence and fills in its receipt. Returns
+/// None, and rejects any receipt, when nothing was burned.
+#[allow(clippy::too_many_arguments)]
+fn record_burn(
+    collateral_config: &mut CollateralConfig,
+    burn_receipt: Option<&mut Account<BurnReceipt>>,
+    burn_type: BurnType,
+    user: Pubkey,
+    market: Pubkey,
+    nonce: u64,
+    amount: u64,
+    burn_amount: u64,
+) -> Result<Option<u64>> {
+    if burn_amount == 0 {
+        require!(burn_receipt.is_none(), ErrorCode::UnexpectedBurnReceipt);
+        return Ok(None);
+    }
+    let burn_receipt = burn_receipt.ok_or(ErrorCode::BurnReceiptRequired)?;
+    let sequence = collateral_config.next_burn_sequence()?;
+    burn_receipt.record(sequence, collateral_config.mint, burn_type, user, market, nonce, amount, burn_amount)?;
+    Ok(Some(sequence))
+}
+
+/// Amount a Token-2022 transfer-fee mint withholds when `amount` is
+/// transferred in the current epoch. Zero for classic SPL mints.
+fn transfer_fee_for(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::CalculationError.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Binds the user to the referrer on their first bet in the mint and returns
+/// the referrer's balance to credit. Rejects self-referral, referrer changes,
+/// binding a referrer after the user has already bet without one and bets
+/// that leave out a bound referrer.
+fn bind_referral<'a>(
+    referral: &mut Account<Referral>,
+    referrer_rewards: Option<&'a mut Account<ReferrerRewards>>,
+    user_stats: &UserStats,
+    user: Pubkey,
+) -> Result<Option<&'a mut ReferrerRewards>> {
+    let is_bound = referral.user != Pubkey::default();
+    let referrer_rewards = match referrer_rewards {
+        Some(referrer_rewards) => referrer_rewards,
+        None if is_bound => return Err(ErrorCode::ReferrerRewardsRequired.into()),
+        None => return Ok(None),
+    };
+    require!(referrer_rewards.referrer != user, ErrorCode::SelfReferral);
+    
+    if !is_bound {
+        require!(user_stats.bet_count == 0, ErrorCode::ReferrerChangeNotAllowed);
+        let timestamp = Clock::get()?.unix_timestamp;
+        referral.user = user;
+        referral.mint = referrer_rewards.mint;
+        referral.referrer = referrer_rewards.referrer;
+        referral.bound_at = timestamp;
+        referrer_rewards.referral_count = referrer_rewards.referral_count
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        msg!("Referral bound: {} -> {}", user, referral.referrer);
+        
+        emit!(ReferralBound {
+            user,
+            mint: referral.mint,
+            referrer: referral.referrer,
+            timestamp,
+        });
+    } else {
+        require_keys_eq!(
+            referral.referrer,
+            referrer_rewards.referrer,
+            ErrorCode::ReferrerChangeNotAllowed
+        );
+    }
+    Ok(Some(&mut **referrer_rewards))
+}
+
+/// Emits the per-recipient breakdown of a fee booked into the fee ledger
+fn emit_fees_accrued(market: Pubkey, source: FeeSource, fee: u64, shares: &FeeShares) -> Result<()> {
+    emit!(FeesAccrued {
+        market,
+        source,
+        fee,
+        treasury: shares.treasury,
+        creator: shares.creator,
+        referrer: shares.referrer,
+        stakers: shares.stakers,
+        sponsors: shares.sponsors,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Reads a market passed as a remaining account
+fn load_market(market_info: &AccountInfo) -> Result<Market> {
+    require!(market_info.owner == &crate::ID, ErrorCode::InvalidParlayLegs);
+    let data = market_info.try_borrow_data()?;
+    Market::try_deserialize(&mut &data[..])
+}
+
+/// Frees the creator's active market slot once a market leaves the Active state
+fn release_creator_quota(
+    market: &mut Market,
+    creator_config: Option<&mut Account<CreatorConfig>>,
+) -> Result<()> {
+    if !market.tracks_creator_quota {
+        return Ok(());
+    }
+    let creator_config = creator_config.ok_or(ErrorCode::CreatorConfigRequired)?;
+    creator_config.close_market();
+    market.tracks_creator_quota = false;
+    Ok(())
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Unauthorized access")]
@@ -996,4 +4545,102 @@ pub enum ErrorCode {
     InsufficientEscrowBalance,
     #[msg("Market is not cancelled")]
     MarketNotCancelled,
+    #[msg("Creator config account required")]
+    CreatorConfigRequired,
+    #[msg("Creator stake below configured minimum")]
+    CreatorStakeTooLow,
+    #[msg("Category not allowed for this creator")]
+    CategoryNotAllowed,
+    #[msg("Creator has reached the active market limit")]
+    ActiveMarketLimitReached,
+    #[msg("Too many categories")]
+    TooManyCategories,
+    #[msg("Creator bond below required amount")]
+    BondTooLow,
+    #[msg("Market is not pending review")]
+    MarketNotPendingReview,
+    #[msg("Market is not resolved or cancelled")]
+    MarketNotFinalized,
+    #[msg("Market is not paused")]
+    MarketNotPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Market creation is paused")]
+    CreationPaused,
+    #[msg("Betting is paused")]
+    BettingPaused,
+    #[msg("Market resolution is paused")]
+    ResolutionPaused,
+    #[msg("Payouts are paused")]
+    PayoutsPaused,
+    #[msg("Refunds are paused")]
+    RefundsPaused,
+    #[msg("Amount exceeds recoverable escrow surplus")]
+    InsufficientSurplus,
+    #[msg("Insufficient treasury balance")]
+    InsufficientTreasuryBalance,
+    #[msg("Burn receipt account is required")]
+    BurnReceiptRequired,
+    #[msg("Burn receipt supplied but nothing was burned")]
+    UnexpectedBurnReceipt,
+    #[msg("Burn already acknowledged")]
+    BurnAlreadyAcknowledged,
+    #[msg("Invalid proof id")]
+    InvalidProofId,
+    #[msg("Invalid fee split table")]
+    InvalidFeeSplit,
+    #[msg("Amount exceeds accrued fees")]
+    InsufficientAccruedFees,
+    #[msg("Bets must credit the referrer the user is bound to")]
+    ReferrerRewardsRequired,
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referrer cannot be changed once bound")]
+    ReferrerChangeNotAllowed,
+    #[msg("Invalid fee tier table")]
+    InvalidFeeTier,
+    #[msg("Invalid points discount table")]
+    InvalidPointsTier,
+    #[msg("Collateral mint is not enabled")]
+    CollateralNotEnabled,
+    #[msg("Position exceeds the market's per-user stake limit")]
+    PositionLimitExceeded,
+    #[msg("Position exceeds the market's max share of the option pool")]
+    OptionShareExceeded,
+    #[msg("Invalid market limits")]
+    InvalidMarketLimits,
+    #[msg("Liquidity pool is inactive")]
+    PoolInactive,
+    #[msg("Swap output below minimum amount")]
+    SlippageTooHigh,
+    #[msg("Subsidy bonus must cover the mint's transfer fee")]
+    SubsidyBonusTooSmall,
+    #[msg("Subsidy can only be withdrawn after cancellation or with no winners")]
+    SubsidyNotRefundable,
+    #[msg("Invalid parlay house configuration")]
+    InvalidParlayConfig,
+    #[msg("Invalid parlay legs")]
+    InvalidParlayLegs,
+    #[msg("No stakes on this option to price a parlay leg")]
+    ParlayOddsUnavailable,
+    #[msg("Parlay house liquidity cannot cover this payout")]
+    InsufficientHouseLiquidity,
+    #[msg("Parlay legs are not all resolved or cancelled")]
+    ParlayNotSettleable,
+    #[msg("Burn has not been acknowledged by the relayer")]
+    BurnNotAcknowledged,
+    #[msg("Buyback pool needs a positive minimum price and a free pool slot")]
+    InvalidBuybackPool,
+    #[msg("Buyback minimum output is below the pinned pool's minimum price")]
+    BuybackMinimumTooLow,
+    #[msg("Leg market's pool is below the parlay house minimum")]
+    ParlayLegTooThin,
+    #[msg("Parlay is not settled")]
+    ParlayNotSettled,
+    #[msg("Buyback pool is not the one pinned for the input mint")]
+    BuybackPoolNotSet,
+    #[msg("Account is not a program account of the expected type")]
+    InvalidAccountLayout,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyMigrated,
 }
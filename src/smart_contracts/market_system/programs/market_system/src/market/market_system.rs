@@ -1,8 +1 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use anchor_lang::solana_program::program::invoke;
-use anchor_lang::solana_program::system_instruction;
-use crate::access_control::AccessControl;
-use crate::{MarketType, MarketStatus, Market, Prediction, PlatformState};
-
 // All structs and enums have been moved to lib.rs
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::fees::FeeSplit;
+use crate::{ErrorCode, Market, MarketStatus, MarketType, PlatformState};
+
+// Layouts of accounts created by the first deployment, before fields were
+// appended to Market and PlatformState. Such accounts are shorter than the
+// current LEN and fail to deserialize, so every instruction rejects them
+// until migrate_platform_state / migrate_market rewrite them in place.
+
+/// PlatformState as first deployed
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyPlatformState {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub bet_burn_rate: u64,
+    pub claim_burn_rate: u64,
+    pub platform_fee_rate: u64,
+    pub total_burned: u64,
+    pub total_volume: u64,
+    pub is_paused: bool,
+}
+
+impl LegacyPlatformState {
+    pub const LEN: usize = 32 + // authority
+                           32 + // token_mint
+                           32 + // treasury
+                           8 +  // bet_burn_rate
+                           8 +  // claim_burn_rate
+                           8 +  // platform_fee_rate
+                           8 +  // total_burned
+                           8 +  // total_volume
+                           1;   // is_paused
+
+    /// The current layout, with the appended settings at their
+    /// `initialize` defaults. A paused platform keeps creation and betting
+    /// paused, the only things the old flag stopped, so resolution and
+    /// payouts carry on.
+    pub fn migrate(self) -> PlatformState {
+        PlatformState {
+            authority: self.authority,
+            token_mint: self.token_mint,
+            treasury: self.treasury,
+            bet_burn_rate: self.bet_burn_rate,
+            claim_burn_rate: self.claim_burn_rate,
+            platform_fee_rate: self.platform_fee_rate,
+            total_burned: self.total_burned,
+            total_volume: self.total_volume,
+            pause_flags: if self.is_paused {
+                PlatformState::PAUSE_CREATION | PlatformState::PAUSE_BETTING
+            } else {
+                0
+            },
+            permissionless_creation: false,
+            permissionless_bond: 0,
+            burn_relayer: Pubkey::default(),
+            fee_splits: FeeSplit::defaults(),
+            fee_tiers: Vec::new(),
+            pmp_mint: Pubkey::default(),
+            points_tiers: Vec::new(),
+            buyback_crank: Pubkey::default(),
+            buyback_pools: Vec::new(),
+        }
+    }
+}
+
+/// Market as first deployed
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyMarket {
+    pub authority: Pubkey,
+    pub creator: Pubkey,
+    pub market_type: MarketType,
+    pub question: [u8; 200],
+    pub question_len: u16,
+    pub option_1: [u8; 50],
+    pub option_2: [u8; 50],
+    pub option_3: [u8; 50],
+    pub option_4: [u8; 50],
+    pub option_count: u8,
+    pub resolution_date: i64,
+    pub creator_fee_rate: u64,
+    pub min_bet_amount: u64,
+    pub token_mint: Pubkey,
+    pub status: MarketStatus,
+    pub winning_option: Option<u8>,
+    pub option_1_pool: u64,
+    pub option_2_pool: u64,
+    pub option_3_pool: u64,
+    pub option_4_pool: u64,
+    pub total_pool: u64,
+    pub market_id: [u8; 32],
+    pub category: [u8; 20],
+}
+
+impl LegacyMarket {
+    pub const LEN: usize = 32 + // authority
+                           32 + // creator
+                           33 + // market_type (enum with padding)
+                           200 + // question
+                           2 +  // question_len
+                           50 + // option_1
+                           50 + // option_2
+                           50 + // option_3
+                           50 + // option_4
+                           1 +  // option_count
+                           8 +  // resolution_date
+                           8 +  // creator_fee_rate
+                           8 +  // min_bet_amount
+                           32 + // token_mint
+                           33 + // status (enum with padding)
+                           2 +  // winning_option (Option<u8>)
+                           8 +  // option_1_pool
+                           8 +  // option_2_pool
+                           8 +  // option_3_pool
+                           8 +  // option_4_pool
+                           8 +  // total_pool
+                           32 + // market_id
+                           20;  // category
+
+    /// The current layout. Legacy markets carried no bond, stake, caps or
+    /// subsidy, so those fields start at zero. Payouts weren't tracked: a
+    /// resolved or cancelled market has paid out whatever of its pool
+    /// `escrow_balance` no longer holds, which keeps the remainder owed.
+    pub fn migrate(self, escrow_balance: u64) -> Market {
+        let paid_out = self.total_pool.saturating_sub(escrow_balance);
+        let (total_claimed, total_refunded) = match self.status {
+            MarketStatus::Resolved => (paid_out, 0),
+            MarketStatus::Cancelled => (0, paid_out),
+            _ => (0, 0),
+        };
+        Market {
+            authority: self.authority,
+            creator: self.creator,
+            market_type: self.market_type,
+            question: self.question,
+            question_len: self.question_len,
+            option_1: self.option_1,
+            option_2: self.option_2,
+            option_3: self.option_3,
+            option_4: self.option_4,
+            option_count: self.option_count,
+            resolution_date: self.resolution_date,
+            creator_fee_rate: self.creator_fee_rate,
+            min_bet_amount: self.min_bet_amount,
+            token_mint: self.token_mint,
+            status: self.status,
+            winning_option: self.winning_option,
+            option_1_pool: self.option_1_pool,
+            option_2_pool: self.option_2_pool,
+            option_3_pool: self.option_3_pool,
+            option_4_pool: self.option_4_pool,
+            total_pool: self.total_pool,
+            market_id: self.market_id,
+            category: self.category,
+            tracks_creator_quota: false,
+            creator_bond: 0,
+            total_claimed,
+            total_refunded,
+            volume: 0,
+            bettor_count: 0,
+            fees_collected: 0,
+            creator_fees_accrued: 0,
+            max_user_stake: 0,
+            max_option_share_bps: 0,
+            subsidy_bonus: 0,
+            subsidy_seed: [0; 4],
+            subsidy_total: 0,
+            subsidy_refunded: 0,
+            sponsor_fee_per_share: 0,
+            creator_stake: 0,
+            pools_slot: 0,
+            settled_pools: [0; 4],
+        }
+    }
+}
+
+/// Returns the legacy body of a program account of type `T` that is still
+/// `8 + legacy_len` bytes long, after checking its discriminator
+pub fn legacy_data<T: Discriminator>(account: &AccountInfo, legacy_len: usize) -> Result<Vec<u8>> {
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 8 && data[..8] == *T::DISCRIMINATOR, ErrorCode::InvalidAccountLayout);
+    require!(data.len() == 8 + legacy_len, ErrorCode::AccountAlreadyMigrated);
+    Ok(data[8..].to_vec())
+}
+
+/// Grows `account` to `new_len` bytes, topping up its rent from `payer`, and
+/// writes `value` over it
+pub fn rewrite<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+    value: &T,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
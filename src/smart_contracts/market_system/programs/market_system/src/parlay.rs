@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use crate::security_checks;
+use crate::{ErrorCode, Market, MarketStatus};
+
+/// House-backed parlay book for one collateral mint. Stakes and house
+/// liquidity share the parlay vault; every open parlay reserves its locked
+/// payout so the house can always pay. Legs are priced off live pools, so
+/// each leg's odds are capped, ignore pool changes from the same slot, and
+/// thin or barely-bet markets are refused.
+#[account]
+#[derive(Default)]
+pub struct ParlayHouse {
+    pub mint: Pubkey,
+    pub edge_bps: u16,              // Taken off the combined odds
+    pub max_multiplier_bps: u64,    // Cap on payout / stake (10000 = 1x)
+    pub balance: u64,               // Vault funds owned by the house, stakes included
+    pub reserved: u64,              // Locked payouts of open parlays
+    pub open_parlays: u32,
+    pub total_staked: u64,
+    pub total_paid: u64,
+    pub max_leg_odds_bps: u64,      // Cap on any one leg's locked odds
+    pub min_leg_pool_tokens: u64,   // Whole tokens a leg market must hold to be priced
+    pub min_leg_bettors: u32,       // Positions a leg market must have taken to be priced
+}
+
+impl ParlayHouse {
+    pub const LEN: usize = 32 + // mint
+                           2 +  // edge_bps
+                           8 +  // max_multiplier_bps
+                           8 +  // balance
+                           8 +  // reserved
+                           4 +  // open_parlays
+                           8 +  // total_staked
+                           8 +  // total_paid
+                           8 +  // max_leg_odds_bps
+                           8 +  // min_leg_pool_tokens
+                           4;   // min_leg_bettors
+
+    pub const MAX_EDGE_BPS: u16 = 2000;
+
+    /// Odds to lock for a leg in `slot`: the market's multiple, capped at
+    /// `max_leg_odds_bps`, on a market holding at least `min_leg_pool_tokens`
+    /// from at least `min_leg_bettors` positions
+    pub fn leg_odds(
+        &self,
+        market: &Market,
+        option_index: u8,
+        decimals: u8,
+        claim_burn_rate: u64,
+        slot: u64,
+    ) -> Result<u64> {
+        require!(
+            market.total_pool >= security_checks::scale_to_mint_units(self.min_leg_pool_tokens, decimals)?
+                && market.bettor_count >= self.min_leg_bettors,
+            ErrorCode::ParlayLegTooThin
+        );
+        Ok(ParlayLeg::odds_for(market, option_index, claim_burn_rate, slot)?.min(self.max_leg_odds_bps))
+    }
+
+    /// Balance not backing any open parlay
+    pub fn available(&self) -> Result<u64> {
+        security_checks::safe_sub(self.balance, self.reserved)
+    }
+
+    /// Books a new parlay's stake and reserves its payout
+    pub fn book(&mut self, stake: u64, payout: u64) -> Result<()> {
+        self.balance = security_checks::safe_add(self.balance, stake)?;
+        self.reserved = security_checks::safe_add(self.reserved, payout)?;
+        require!(self.reserved <= self.balance, ErrorCode::InsufficientHouseLiquidity);
+        self.open_parlays = self.open_parlays
+            .checked_add(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.total_staked = security_checks::safe_add(self.total_staked, stake)?;
+        Ok(())
+    }
+
+    /// Releases a settled parlay's reservation and books what it paid
+    pub fn settle(&mut self, reserved_payout: u64, paid: u64) -> Result<()> {
+        self.reserved = security_checks::safe_sub(self.reserved, reserved_payout)?;
+        self.balance = security_checks::safe_sub(self.balance, paid)?;
+        self.open_parlays = self.open_parlays
+            .checked_sub(1)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.total_paid = security_checks::safe_add(self.total_paid, paid)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LegResult {
+    Pending,
+    Won,
+    Lost,
+    Void,       // Market cancelled; the leg counts as 1x
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParlayLeg {
+    pub market: Pubkey,
+    pub option_index: u8,
+    pub odds_bps: u64,          // Locked payout multiple for this leg (10000 = 1x)
+    pub result: LegResult,
+}
+
+impl ParlayLeg {
+    pub const LEN: usize = 32 + // market
+                           1 +  // option_index
+                           8 +  // odds_bps
+                           1;   // result
+
+    /// Parimutuel multiple of an option in `slot`: the pool winners would
+    /// share, less the creator fee and `claim_burn_rate` taken at claim, over
+    /// the stakes on that option. The sponsor bonus is left out, and so are
+    /// pool changes made in `slot` when they would raise the multiple.
+    pub fn odds_for(market: &Market, option_index: u8, claim_burn_rate: u64, slot: u64) -> Result<u64> {
+        security_checks::validate_option_index(option_index, market.option_count)?;
+        let claim_fee_rate = security_checks::safe_add(market.creator_fee_rate, claim_burn_rate)?;
+        let live = Self::pool_odds(&market.option_pools(), market, option_index, claim_fee_rate)?;
+        let settled = Self::pool_odds(&market.settled_option_pools(slot), market, option_index, claim_fee_rate)?;
+        Ok(live.min(settled))
+    }
+
+    fn pool_odds(pools: &[u64; 4], market: &Market, option_index: u8, claim_fee_rate: u64) -> Result<u64> {
+        let option_stake = pools[option_index as usize]
+            .saturating_sub(market.subsidy_seed[option_index as usize]);
+        require!(option_stake > 0, ErrorCode::ParlayOddsUnavailable);
+        let total_pool = pools.iter().try_fold(0u64, |total, pool| security_checks::safe_add(total, *pool))?;
+        let net_pool = security_checks::safe_div(
+            security_checks::safe_mul(total_pool, 10000u64.saturating_sub(claim_fee_rate))?,
+            10000
+        )?;
+        let odds = (net_pool as u128)
+            .checked_mul(10000)
+            .ok_or(ErrorCode::CalculationError)?
+            / option_stake as u128;
+        u64::try_from(odds).map_err(|_| ErrorCode::CalculationError.into())
+    }
+
+    /// Outcome of the leg once its market is final, None while it is open
+    pub fn result_for(&self, market: &Market) -> Option<LegResult> {
+        match market.status {
+            MarketStatus::Resolved => Some(if market.winning_option == Some(self.option_index) {
+                LegResult::Won
+            } else {
+                LegResult::Lost
+            }),
+            MarketStatus::Cancelled => Some(LegResult::Void),
+            _ => None,
+        }
+    }
+}
+
+/// A single stake on every leg winning. Odds are locked per leg when placed.
+/// Legs still open at `expires_at` are voided on settlement.
+#[account]
+#[derive(Default)]
+pub struct Parlay {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub parlay_id: u64,
+    pub stake: u64,
+    pub payout: u64,            // Payout if every leg wins, reserved in the house
+    pub edge_bps: u16,          // House edge when placed
+    pub legs: Vec<ParlayLeg>,
+    pub placed_at: i64,
+    pub settled: bool,
+    pub paid_out: u64,
+    pub expires_at: i64,        // Latest leg resolution date plus EXPIRY_GRACE
+}
+
+impl Parlay {
+    pub const MIN_LEGS: usize = 2;
+    pub const MAX_LEGS: usize = 5;
+    pub const EXPIRY_GRACE: i64 = 30 * 24 * 60 * 60;
+    pub const LEN: usize = 32 + // user
+                           32 + // mint
+                           8 +  // parlay_id
+                           8 +  // stake
+                           8 +  // payout
+                           2 +  // edge_bps
+                           4 + (ParlayLeg::LEN * Self::MAX_LEGS) + // vec len + legs
+                           8 +  // placed_at
+                           1 +  // settled
+                           8 +  // paid_out
+                           8;   // expires_at
+
+    /// Outcome of each leg at `now`, given its market. Open legs are void once
+    /// the parlay has expired, and otherwise keep it from settling.
+    pub fn leg_result(&self, leg: &ParlayLeg, market: &Market, now: i64) -> Result<LegResult> {
+        match leg.result_for(market) {
+            Some(result) => Ok(result),
+            None if now >= self.expires_at => Ok(LegResult::Void),
+            None => Err(ErrorCode::ParlayNotSettleable.into()),
+        }
+    }
+
+    /// Stake times the combined odds of the counted legs, less the house edge,
+    /// capped at `cap`. With every leg void the stake is simply returned.
+    pub fn payout_for(stake: u64, legs: &[ParlayLeg], edge_bps: u16, cap: u64) -> Result<u64> {
+        let mut counted = legs.iter().filter(|leg| leg.result != LegResult::Void).peekable();
+        if counted.peek().is_none() {
+            return Ok(stake);
+        }
+        let mut payout = stake as u128;
+        for leg in counted {
+            payout = payout
+                .checked_mul(leg.odds_bps as u128)
+                .ok_or(ErrorCode::CalculationError)?
+                / 10000;
+        }
+        payout = payout
+            .checked_mul(10000 - edge_bps as u128)
+            .ok_or(ErrorCode::CalculationError)?
+            / 10000;
+        Ok(u64::try_from(payout).unwrap_or(u64::MAX).min(cap))
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Binds a user to the referrer credited on their bets in one mint. Set
+/// once, on the user's first bet in that mint, and never changed afterwards.
+#[account]
+#[derive(Default)]
+pub struct Referral {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub referrer: Pubkey,
+    pub bound_at: i64,
+}
+
+impl Referral {
+    pub const LEN: usize = 32 + // user
+                           32 + // mint
+                           32 + // referrer
+                           8;   // bound_at
+}
+
+/// A referrer's claimable share of platform fees for one mint. Registered by
+/// the referrer before it can be attached to a bet.
+#[account]
+#[derive(Default)]
+pub struct ReferrerRewards {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub accrued: u64,          // Claimable from the treasury vault
+    pub total_earned: u64,
+    pub referral_count: u32,   // Users bound to this referrer through this mint
+}
+
+impl ReferrerRewards {
+    pub const LEN: usize = 32 + // referrer
+                           32 + // mint
+                           8 +  // accrued
+                           8 +  // total_earned
+                           4;   // referral_count
+}
@@ -1,11 +1,5 @@
 use anchor_lang::prelude::*;
 
-/// Maximum pool size to prevent overflow attacks (100 billion tokens)
-pub const MAX_TOTAL_POOL_SIZE: u64 = 100_000_000_000 * 1_000_000; // 100B with 6 decimals (~$20M)
-
-/// Maximum bet amount per transaction (1 billion tokens) - ~$204,000 at current prices
-pub const MAX_BET_AMOUNT: u64 = 1_000_000_000 * 1_000_000; // 1B APES with 6 decimals
-
 /// Maximum fee rate (10% = 1000 basis points)
 pub const MAX_FEE_RATE: u64 = 1000;
 
@@ -24,27 +18,35 @@ pub fn validate_option_index(option_index: u8, option_count: u8) -> Result<()> {
     Ok(())
 }
 
+/// Converts a whole-token amount into base units for a mint with `decimals`
+pub fn scale_to_mint_units(whole_tokens: u64, decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| whole_tokens.checked_mul(unit))
+        .ok_or(ErrorCode::CalculationError.into())
+}
+
 /// Validates bet amount is within acceptable limits
-pub fn validate_bet_amount(amount: u64, min_bet: u64) -> Result<()> {
+pub fn validate_bet_amount(amount: u64, min_bet: u64, max_bet: u64) -> Result<()> {
     require!(
         amount >= min_bet,
         ErrorCode::BetTooSmall
     );
     require!(
-        amount <= MAX_BET_AMOUNT,
+        amount <= max_bet,
         ErrorCode::BetTooLarge
     );
     Ok(())
 }
 
-/// Validates that adding to pool won't cause overflow
-pub fn validate_pool_addition(current_pool: u64, amount_to_add: u64) -> Result<()> {
+/// Validates that adding to pool won't cause overflow or exceed the pool cap
+pub fn validate_pool_addition(current_pool: u64, amount_to_add: u64, max_pool: u64) -> Result<()> {
     let new_total = current_pool
         .checked_add(amount_to_add)
         .ok_or(ErrorCode::CalculationError)?;
     
     require!(
-        new_total <= MAX_TOTAL_POOL_SIZE,
+        new_total <= max_pool,
         ErrorCode::PoolOverflow
     );
     
@@ -103,6 +105,12 @@ pub fn safe_sub(a: u64, b: u64) -> Result<u64> {
         .ok_or(ErrorCode::CalculationError.into())
 }
 
+/// Safe addition with overflow check
+pub fn safe_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .ok_or(ErrorCode::CalculationError.into())
+}
+
 /// Validates string length for fixed arrays
 pub fn validate_string_length(actual_len: usize, max_len: usize) -> Result<()> {
     require!(
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::security_checks;
+use crate::ErrorCode;
+
+/// One sponsor's subsidy of one market. The bonus and seed amounts are what
+/// arrived in the escrow; together they weight the sponsor's share of the
+/// market's sponsor fees.
+#[account]
+#[derive(Default)]
+pub struct Sponsorship {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub bonus_amount: u64,     // Paid to winners on top of the pool
+    pub seed_amount: u64,      // Added to option pools, paid to winners
+    pub reward_debt: u128,     // Sponsor fees already counted, scaled by FEE_SCALE
+    pub fees_owed: u64,        // Settled but unclaimed sponsor fees
+    pub fees_claimed: u64,
+    pub refunded: bool,        // Subsidy returned after cancellation or no winners
+}
+
+impl Sponsorship {
+    pub const LEN: usize = 32 + // market
+                           32 + // sponsor
+                           8 +  // bonus_amount
+                           8 +  // seed_amount
+                           16 + // reward_debt
+                           8 +  // fees_owed
+                           8 +  // fees_claimed
+                           1;   // refunded
+
+    /// Precision of `Market::sponsor_fee_per_share`
+    pub const FEE_SCALE: u128 = 1_000_000_000_000;
+
+    pub fn weight(&self) -> Result<u64> {
+        security_checks::safe_add(self.bonus_amount, self.seed_amount)
+    }
+
+    fn accumulated(&self, fee_per_share: u128) -> Result<u128> {
+        (self.weight()? as u128)
+            .checked_mul(fee_per_share)
+            .map(|scaled| scaled / Self::FEE_SCALE)
+            .ok_or(ErrorCode::CalculationError.into())
+    }
+
+    /// Moves fees earned since the last settlement into `fees_owed`. Must be
+    /// called before the weight changes.
+    pub fn settle(&mut self, fee_per_share: u128) -> Result<()> {
+        let accumulated = self.accumulated(fee_per_share)?;
+        let pending = u64::try_from(accumulated.saturating_sub(self.reward_debt))
+            .map_err(|_| ErrorCode::CalculationError)?;
+        self.fees_owed = security_checks::safe_add(self.fees_owed, pending)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+
+    /// Marks everything earned so far at the current weight as counted.
+    /// Called after the weight changes.
+    pub fn rebase(&mut self, fee_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accumulated(fee_per_share)?;
+        Ok(())
+    }
+}
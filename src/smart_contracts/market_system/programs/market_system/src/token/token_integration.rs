@@ -1,8 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use anchor_lang::solana_program::program::invoke;
-use anchor_lang::solana_program::system_instruction;
 
+#[allow(clippy::module_inception)]
 pub mod token_integration {
     use super::*;
 
@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 
 // BelieveApp integration for token burning
 // This module emits events that are processed off-chain by a backend service
-// that calls the BelieveApp API
+// that calls the BelieveApp API. Each burn carries its mint and a per-mint
+// sequence number so the backend can process every burn exactly once.
 
 #[event]
 pub struct BurnEvent {
@@ -13,18 +14,22 @@ pub struct BurnEvent {
     pub burn_amount: u64,
     pub prediction_option: Option<String>,
     pub timestamp: i64,
-    pub transaction_signature: [u8; 64],
+    pub sequence: u64,
+    pub mint: Pubkey,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BurnType {
     PredictionBet,
     RewardClaim,
     MarketCreation,
+    Buyback,
 }
 
 // Process bet burn - emits event for off-chain processing
 pub fn process_bet_burn(
+    sequence: u64,
+    mint: Pubkey,
     user: Pubkey,
     market: Pubkey,
     prediction_option: String,
@@ -34,10 +39,6 @@ pub fn process_bet_burn(
     // Get current timestamp
     let timestamp = Clock::get()?.unix_timestamp;
     
-    // In a real implementation, we would get the transaction signature
-    // For now, we use a placeholder
-    let transaction_signature = [0u8; 64];
-    
     // Emit burn event for off-chain processing
     emit!(BurnEvent {
         burn_type: BurnType::PredictionBet,
@@ -47,24 +48,26 @@ pub fn process_bet_burn(
         burn_amount,
         prediction_option: Some(prediction_option),
         timestamp,
-        transaction_signature,
+        sequence,
+        mint,
     });
     
-    msg!("Burn event emitted: PREDICTION_BET, user: {}, market: {}, burn_amount: {}", 
-        user, market, burn_amount);
+    msg!("Burn event emitted: PREDICTION_BET, seq: {}, user: {}, market: {}, burn_amount: {}", 
+        sequence, user, market, burn_amount);
     
     Ok(())
 }
 
 // Process reward claim burn - emits event for off-chain processing
 pub fn process_reward_burn(
+    sequence: u64,
+    mint: Pubkey,
     user: Pubkey,
     market: Pubkey,
     amount: u64,
     burn_amount: u64,
 ) -> Result<()> {
     let timestamp = Clock::get()?.unix_timestamp;
-    let transaction_signature = [0u8; 64];
     
     emit!(BurnEvent {
         burn_type: BurnType::RewardClaim,
@@ -74,24 +77,26 @@ pub fn process_reward_burn(
         burn_amount,
         prediction_option: None,
         timestamp,
-        transaction_signature,
+        sequence,
+        mint,
     });
     
-    msg!("Burn event emitted: REWARD_CLAIM, user: {}, market: {}, burn_amount: {}", 
-        user, market, burn_amount);
+    msg!("Burn event emitted: REWARD_CLAIM, seq: {}, user: {}, market: {}, burn_amount: {}", 
+        sequence, user, market, burn_amount);
     
     Ok(())
 }
 
 // Process market creation burn - emits event for off-chain processing
 pub fn process_market_creation_burn(
+    sequence: u64,
+    mint: Pubkey,
     creator: Pubkey,
     market: Pubkey,
     stake_amount: u64,
     burn_amount: u64,
 ) -> Result<()> {
     let timestamp = Clock::get()?.unix_timestamp;
-    let transaction_signature = [0u8; 64];
     
     emit!(BurnEvent {
         burn_type: BurnType::MarketCreation,
@@ -101,11 +106,41 @@ pub fn process_market_creation_burn(
         burn_amount,
         prediction_option: None,
         timestamp,
-        transaction_signature,
+        sequence,
+        mint,
+    });
+    
+    msg!("Burn event emitted: MARKET_CREATION, seq: {}, creator: {}, market: {}, burn_amount: {}", 
+        sequence, creator, market, burn_amount);
+    
+    Ok(())
+}
+
+// Process treasury buyback burn - emits event for off-chain processing.
+// There is no market; `amount` is the treasury spend in input-mint units.
+pub fn process_buyback_burn(
+    sequence: u64,
+    mint: Pubkey,
+    caller: Pubkey,
+    amount: u64,
+    burn_amount: u64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    
+    emit!(BurnEvent {
+        burn_type: BurnType::Buyback,
+        user: caller,
+        market: Pubkey::default(),
+        amount,
+        burn_amount,
+        prediction_option: None,
+        timestamp,
+        sequence,
+        mint,
     });
     
-    msg!("Burn event emitted: MARKET_CREATION, creator: {}, market: {}, burn_amount: {}", 
-        creator, market, burn_amount);
+    msg!("Burn event emitted: BUYBACK, seq: {}, caller: {}, burn_amount: {}", 
+        sequence, caller, burn_amount);
     
     Ok(())
 }
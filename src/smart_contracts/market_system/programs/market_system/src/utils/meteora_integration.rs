@@ -1,37 +1,203 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_lang::solana_program::{hash, instruction::{AccountMeta, Instruction}, program::invoke_signed, pubkey};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
+use crate::events::{LiquidityAdded, LiquidityRemoved, TokensSwapped};
+use crate::PlatformState;
 
+/// Meteora Dynamic AMM program
+pub const METEORA_AMM_PROGRAM_ID: Pubkey = pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+
+/// Meteora vault program holding Dynamic AMM pool reserves
+pub const METEORA_VAULT_PROGRAM_ID: Pubkey = pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+
+/// Maximum swap fee (10% = 1000 basis points)
+pub const MAX_SWAP_FEE_BPS: u16 = 1000;
+
+/// LP units locked by the first deposit so the supply can never return to
+/// zero and the share price can't be inflated by a dust first deposit
+pub const MINIMUM_LIQUIDITY: u64 = 1000;
+
+/// Constant-product output (x * y = k) for `input_amount` paid into a pool
+/// holding `input_reserve` and `output_reserve`, after taking `fee_bps` off
+/// the input. Computed in u128 so large reserves don't overflow.
+pub fn quote_swap(
+    input_reserve: u64,
+    output_reserve: u64,
+    input_amount: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    require!(input_reserve > 0 && output_reserve > 0, ErrorCode::InsufficientLiquidity);
+    let input_after_fee = (input_amount as u128)
+        .checked_mul(10000 - fee_bps as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        / 10000;
+    let k = (input_reserve as u128)
+        .checked_mul(output_reserve as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let new_input_reserve = (input_reserve as u128)
+        .checked_add(input_after_fee)
+        .ok_or(ErrorCode::CalculationError)?;
+    // Round the remaining reserve up so rounding never favours the swapper
+    let new_output_reserve = k
+        .checked_add(new_input_reserve - 1)
+        .ok_or(ErrorCode::CalculationError)?
+        / new_input_reserve;
+    let output_amount = (output_reserve as u128)
+        .checked_sub(new_output_reserve)
+        .ok_or(ErrorCode::CalculationError)?;
+    u64::try_from(output_amount).map_err(|_| ErrorCode::CalculationError.into())
+}
+
+/// `amount * numerator / denominator`, rounded down or up
+fn mul_div(amount: u64, numerator: u64, denominator: u64, round_up: bool) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::InsufficientLiquidity);
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let quotient = if round_up {
+        product
+            .checked_add(denominator as u128 - 1)
+            .ok_or(ErrorCode::CalculationError)?
+            / denominator as u128
+    } else {
+        product / denominator as u128
+    };
+    u64::try_from(quotient).map_err(|_| ErrorCode::CalculationError.into())
+}
+
+/// Integer square root (floor), by Newton's method
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Swaps `in_amount` from `source` into `destination` through a Meteora
+/// Dynamic AMM pool. `authority` owns `source`; `signer_seeds` are used when
+/// it is a PDA. Returns the amount that actually arrived in `destination`,
+/// which must be at least `minimum_out_amount` regardless of what the pool
+/// program enforces.
+#[allow(clippy::too_many_arguments)]
+pub fn meteora_swap<'info>(
+    pool: &MeteoraSwap<'info>,
+    source: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    in_amount: u64,
+    minimum_out_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    require!(in_amount > 0, ErrorCode::InvalidAmount);
+    let balance_before = token_balance(&destination)?;
+    
+    let mut data = hash::hash(b"global:swap").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&in_amount.to_le_bytes());
+    data.extend_from_slice(&minimum_out_amount.to_le_bytes());
+    let account_infos = vec![
+        pool.pool.to_account_info(),
+        source,
+        destination.clone(),
+        pool.a_vault.to_account_info(),
+        pool.b_vault.to_account_info(),
+        pool.a_token_vault.to_account_info(),
+        pool.b_token_vault.to_account_info(),
+        pool.a_vault_lp_mint.to_account_info(),
+        pool.b_vault_lp_mint.to_account_info(),
+        pool.a_vault_lp.to_account_info(),
+        pool.b_vault_lp.to_account_info(),
+        pool.protocol_token_fee.to_account_info(),
+        authority,
+        pool.vault_program.to_account_info(),
+        token_program,
+    ];
+    // Everything is writable except the signer and the two programs; the
+    // authority is marked as signer explicitly since it may be a PDA
+    let last_writable = account_infos.len() - 4;
+    let instruction = Instruction {
+        program_id: METEORA_AMM_PROGRAM_ID,
+        accounts: account_infos
+            .iter()
+            .enumerate()
+            .map(|(i, info)| match i {
+                i if i <= last_writable => AccountMeta::new(info.key(), false),
+                i if i == last_writable + 1 => AccountMeta::new_readonly(info.key(), true),
+                _ => AccountMeta::new_readonly(info.key(), false),
+            })
+            .collect(),
+        data,
+    };
+    let mut account_infos = account_infos;
+    account_infos.push(pool.amm_program.to_account_info());
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+    
+    let received = token_balance(&destination)?
+        .checked_sub(balance_before)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(received >= minimum_out_amount, ErrorCode::SlippageTooHigh);
+    
+    msg!("Meteora swap: {} in, {} out", in_amount, received);
+    Ok(received)
+}
+
+/// Balance of an SPL or Token-2022 token account
+fn token_balance(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(token_interface::TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+#[allow(clippy::module_inception)]
 pub mod meteora_integration {
     use super::*;
-
+    
     // Initialize liquidity pool integration
     pub fn initialize_liquidity_pool(
         ctx: Context<InitializeLiquidityPool>,
         pool_id: String,
+        swap_fee_bps: u16,
     ) -> Result<()> {
+        require!(pool_id.len() <= LiquidityPoolState::MAX_POOL_ID_LEN, ErrorCode::PoolIdTooLong);
+        require!(swap_fee_bps <= MAX_SWAP_FEE_BPS, ErrorCode::FeeTooHigh);
         let pool_state = &mut ctx.accounts.pool_state;
         
         pool_state.authority = ctx.accounts.authority.key();
         pool_state.token_mint = ctx.accounts.token_mint.key();
         pool_state.usdc_mint = ctx.accounts.usdc_mint.key();
+        pool_state.lp_mint = ctx.accounts.lp_mint.key();
         pool_state.pool_id = pool_id.clone();
         pool_state.is_active = true;
-        pool_state.total_liquidity = 0;
+        pool_state.swap_fee_bps = swap_fee_bps;
+        pool_state.token_reserve = 0;
+        pool_state.usdc_reserve = 0;
+        pool_state.lp_supply = 0;
         
         msg!("Liquidity pool initialized: {}", pool_id);
+        msg!("Swap fee: {}bp", swap_fee_bps);
         
         Ok(())
     }
     
-    // Add liquidity to the pool
+    // Add liquidity to the pool in exchange for LP tokens
     pub fn add_liquidity(
         ctx: Context<ManageLiquidity>,
-        token_amount: u64,
-        usdc_amount: u64,
+        max_token_amount: u64,
+        max_usdc_amount: u64,
+        minimum_lp_amount: u64,
     ) -> Result<()> {
         let pool_state = &mut ctx.accounts.pool_state;
         require!(pool_state.is_active, ErrorCode::PoolInactive);
         
+        let (lp_amount, token_amount, usdc_amount) = pool_state.deposit(max_token_amount, max_usdc_amount)?;
+        require!(lp_amount >= minimum_lp_amount, ErrorCode::SlippageTooHigh);
+        
         // Transfer platform tokens to pool
         token::transfer(
             CpiContext::new(
@@ -58,27 +224,69 @@ pub mod meteora_integration {
             usdc_amount,
         )?;
         
-        // Update pool state
-        pool_state.total_liquidity = pool_state.total_liquidity
-            .checked_add(token_amount)
-            .ok_or(ErrorCode::CalculationError)?;
-            
-        // In a real implementation, we would mint LP tokens to the user
-        // and track their share of the pool
+        // Mint the provider's share of the pool
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.lp_mint.to_account_info(),
+                },
+                &[&[
+                    b"pool_lp_mint",
+                    pool_state.key().as_ref(),
+                    &[ctx.bumps.lp_mint],
+                ]],
+            ),
+            lp_amount,
+        )?;
         
         msg!("Liquidity added: {} tokens, {} USDC", token_amount, usdc_amount);
+        msg!("LP tokens minted: {}", lp_amount);
+        
+        emit!(LiquidityAdded {
+            pool: pool_state.key(),
+            provider: ctx.accounts.user.key(),
+            token_amount,
+            usdc_amount,
+            lp_amount,
+            token_reserve: pool_state.token_reserve,
+            usdc_reserve: pool_state.usdc_reserve,
+            lp_supply: pool_state.lp_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
     
-    // Remove liquidity from the pool
+    // Burn LP tokens to withdraw their share of both reserves. Allowed while
+    // the pool is inactive so providers can always exit.
     pub fn remove_liquidity(
         ctx: Context<ManageLiquidity>,
-        token_amount: u64,
-        usdc_amount: u64,
+        lp_amount: u64,
+        minimum_token_amount: u64,
+        minimum_usdc_amount: u64,
     ) -> Result<()> {
         let pool_state = &mut ctx.accounts.pool_state;
-        require!(pool_state.is_active, ErrorCode::PoolInactive);
+        
+        let (token_amount, usdc_amount) = pool_state.withdraw(lp_amount)?;
+        require!(
+            token_amount >= minimum_token_amount && usdc_amount >= minimum_usdc_amount,
+            ErrorCode::SlippageTooHigh
+        );
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
         
         // Transfer platform tokens from pool to user
         token::transfer(
@@ -116,14 +324,20 @@ pub mod meteora_integration {
             usdc_amount,
         )?;
         
-        // Update pool state
-        pool_state.total_liquidity = pool_state.total_liquidity
-            .checked_sub(token_amount)
-            .ok_or(ErrorCode::CalculationError)?;
-            
-        // In a real implementation, we would burn LP tokens from the user
-        
         msg!("Liquidity removed: {} tokens, {} USDC", token_amount, usdc_amount);
+        msg!("LP tokens burned: {}", lp_amount);
+        
+        emit!(LiquidityRemoved {
+            pool: pool_state.key(),
+            provider: ctx.accounts.user.key(),
+            token_amount,
+            usdc_amount,
+            lp_amount,
+            token_reserve: pool_state.token_reserve,
+            usdc_reserve: pool_state.usdc_reserve,
+            lp_supply: pool_state.lp_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
@@ -135,46 +349,11 @@ pub mod meteora_integration {
         minimum_output_amount: u64,
         is_token_to_usdc: bool,
     ) -> Result<()> {
-        let pool_state = &ctx.accounts.pool_state;
+        let pool_state = &mut ctx.accounts.pool_state;
         require!(pool_state.is_active, ErrorCode::PoolInactive);
         
-        // Get pool token balances
-        let token_balance = ctx.accounts.pool_token_account.amount;
-        let usdc_balance = ctx.accounts.pool_usdc_account.amount;
-        
-        // Calculate output amount based on constant product formula (x * y = k)
-        // This is a simplified version; real AMMs have more complex formulas
-        let output_amount = if is_token_to_usdc {
-            // Token to USDC swap
-            let new_token_balance = token_balance
-                .checked_add(input_amount)
-                .ok_or(ErrorCode::CalculationError)?;
-                
-            let new_usdc_balance = token_balance
-                .checked_mul(usdc_balance)
-                .ok_or(ErrorCode::CalculationError)?
-                .checked_div(new_token_balance)
-                .ok_or(ErrorCode::CalculationError)?;
-                
-            usdc_balance
-                .checked_sub(new_usdc_balance)
-                .ok_or(ErrorCode::CalculationError)?
-        } else {
-            // USDC to Token swap
-            let new_usdc_balance = usdc_balance
-                .checked_add(input_amount)
-                .ok_or(ErrorCode::CalculationError)?;
-                
-            let new_token_balance = token_balance
-                .checked_mul(usdc_balance)
-                .ok_or(ErrorCode::CalculationError)?
-                .checked_div(new_usdc_balance)
-                .ok_or(ErrorCode::CalculationError)?;
-                
-            token_balance
-                .checked_sub(new_token_balance)
-                .ok_or(ErrorCode::CalculationError)?
-        };
+        // Quote against the tracked reserves and book the swap
+        let output_amount = pool_state.apply_swap(is_token_to_usdc, input_amount)?;
         
         // Check minimum output amount
         require!(
@@ -255,6 +434,17 @@ pub mod meteora_integration {
             if is_token_to_usdc { "USDC" } else { "tokens" }
         );
         
+        emit!(TokensSwapped {
+            pool: pool_state.key(),
+            user: ctx.accounts.user.key(),
+            is_token_to_usdc,
+            input_amount,
+            output_amount,
+            token_reserve: pool_state.token_reserve,
+            usdc_reserve: pool_state.usdc_reserve,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
         Ok(())
     }
     
@@ -282,12 +472,19 @@ pub struct InitializeLiquidityPool<'info> {
     )]
     pub pool_state: Account<'info, LiquidityPoolState>,
     
+    #[account(
+        seeds = [b"platform_state"],
+        bump,
+        constraint = platform_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, token::Mint>,
+    pub token_mint: Account<'info, Mint>,
     
-    pub usdc_mint: Account<'info, token::Mint>,
+    pub usdc_mint: Account<'info, Mint>,
     
     #[account(
         init,
@@ -309,6 +506,16 @@ pub struct InitializeLiquidityPool<'info> {
     )]
     pub pool_usdc_account: Account<'info, TokenAccount>,
     
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_lp_mint", pool_state.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = lp_mint,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
@@ -336,6 +543,13 @@ pub struct ManageLiquidity<'info> {
     )]
     pub user_usdc_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        constraint = user_lp_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_lp_account.mint == pool_state.lp_mint @ ErrorCode::InvalidMint
+    )]
+    pub user_lp_account: Account<'info, TokenAccount>,
+    
     #[account(
         mut,
         seeds = [b"pool_token_account", pool_state.key().as_ref()],
@@ -350,11 +564,19 @@ pub struct ManageLiquidity<'info> {
     )]
     pub pool_usdc_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        seeds = [b"pool_lp_mint", pool_state.key().as_ref()],
+        bump,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 pub struct SwapTokens<'info> {
+    #[account(mut)]
     pub pool_state: Account<'info, LiquidityPoolState>,
     
     #[account(mut)]
@@ -391,6 +613,60 @@ pub struct SwapTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Pool-side accounts of a Meteora Dynamic AMM swap. The pool and vault
+/// accounts are validated by the Meteora programs themselves; here we only
+/// pin the program ids and the pool's owner.
+#[derive(Accounts)]
+pub struct MeteoraSwap<'info> {
+    /// CHECK: Must be owned by the Meteora AMM program, which validates it
+    #[account(mut, owner = METEORA_AMM_PROGRAM_ID @ ErrorCode::InvalidSwapProgram)]
+    pub pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora AMM program against the pool
+    #[account(mut)]
+    pub a_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora AMM program against the pool
+    #[account(mut)]
+    pub b_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora vault program
+    #[account(mut)]
+    pub a_token_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora vault program
+    #[account(mut)]
+    pub b_token_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora vault program
+    #[account(mut)]
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora vault program
+    #[account(mut)]
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora AMM program against the pool
+    #[account(mut)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora AMM program against the pool
+    #[account(mut)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by the Meteora AMM program against the pool
+    #[account(mut)]
+    pub protocol_token_fee: UncheckedAccount<'info>,
+    
+    /// CHECK: Address pinned to the Meteora vault program
+    #[account(address = METEORA_VAULT_PROGRAM_ID @ ErrorCode::InvalidSwapProgram)]
+    pub vault_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Address pinned to the Meteora AMM program
+    #[account(address = METEORA_AMM_PROGRAM_ID @ ErrorCode::InvalidSwapProgram)]
+    pub amm_program: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetPoolActive<'info> {
     #[account(
@@ -402,23 +678,106 @@ pub struct SetPoolActive<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Constant-product pool between the platform token and USDC. Reserves are
+/// tracked here rather than read from the vaults, so tokens sent directly to
+/// a vault don't move the price. Providers own the pool pro rata to their LP
+/// tokens; swap fees stay in the reserves and accrue to them.
 #[account]
 pub struct LiquidityPoolState {
     pub authority: Pubkey,
     pub token_mint: Pubkey,
     pub usdc_mint: Pubkey,
+    pub lp_mint: Pubkey,
     pub pool_id: String,
     pub is_active: bool,
-    pub total_liquidity: u64,
+    pub swap_fee_bps: u16,          // Taken from swap input, left in the pool
+    pub token_reserve: u64,
+    pub usdc_reserve: u64,
+    pub lp_supply: u64,             // Outstanding LP tokens plus MINIMUM_LIQUIDITY
 }
 
 impl LiquidityPoolState {
+    pub const MAX_POOL_ID_LEN: usize = 50;
     pub const LEN: usize = 32 + // authority
                            32 + // token_mint
                            32 + // usdc_mint
-                           50 + // pool_id (max 50 chars)
+                           32 + // lp_mint
+                           4 + Self::MAX_POOL_ID_LEN + // pool_id
                            1 +  // is_active
-                           8;   // total_liquidity
+                           2 +  // swap_fee_bps
+                           8 +  // token_reserve
+                           8 +  // usdc_reserve
+                           8;   // lp_supply
+    
+    /// Books a deposit of at most the given amounts. The first deposit sets
+    /// the price; later ones are taken in the current reserve ratio. Returns
+    /// (LP tokens to mint, tokens taken, USDC taken).
+    pub fn deposit(&mut self, max_token_amount: u64, max_usdc_amount: u64) -> Result<(u64, u64, u64)> {
+        let (lp_amount, token_amount, usdc_amount) = if self.lp_supply == 0 {
+            let liquidity = isqrt((max_token_amount as u128) * (max_usdc_amount as u128));
+            let liquidity = u64::try_from(liquidity).map_err(|_| ErrorCode::CalculationError)?;
+            require!(liquidity > MINIMUM_LIQUIDITY, ErrorCode::InsufficientLiquidity);
+            self.lp_supply = MINIMUM_LIQUIDITY;
+            (liquidity - MINIMUM_LIQUIDITY, max_token_amount, max_usdc_amount)
+        } else {
+            let lp_amount = mul_div(max_token_amount, self.lp_supply, self.token_reserve, false)?
+                .min(mul_div(max_usdc_amount, self.lp_supply, self.usdc_reserve, false)?);
+            require!(lp_amount > 0, ErrorCode::InvalidAmount);
+            (
+                lp_amount,
+                mul_div(lp_amount, self.token_reserve, self.lp_supply, true)?,
+                mul_div(lp_amount, self.usdc_reserve, self.lp_supply, true)?,
+            )
+        };
+        self.token_reserve = self.token_reserve
+            .checked_add(token_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.usdc_reserve = self.usdc_reserve
+            .checked_add(usdc_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.lp_supply = self.lp_supply
+            .checked_add(lp_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok((lp_amount, token_amount, usdc_amount))
+    }
+    
+    /// Books the burn of `lp_amount` LP tokens and returns the
+    /// (tokens, USDC) they redeem for, rounded down
+    pub fn withdraw(&mut self, lp_amount: u64) -> Result<(u64, u64)> {
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+        let token_amount = mul_div(lp_amount, self.token_reserve, self.lp_supply, false)?;
+        let usdc_amount = mul_div(lp_amount, self.usdc_reserve, self.lp_supply, false)?;
+        self.lp_supply = self.lp_supply
+            .checked_sub(lp_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(self.lp_supply >= MINIMUM_LIQUIDITY, ErrorCode::InsufficientLiquidity);
+        self.token_reserve = self.token_reserve
+            .checked_sub(token_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.usdc_reserve = self.usdc_reserve
+            .checked_sub(usdc_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok((token_amount, usdc_amount))
+    }
+    
+    /// Quotes a swap against the reserves and books it. The whole input,
+    /// fee included, is added to the reserves, which is how fees reach LPs.
+    pub fn apply_swap(&mut self, is_token_to_usdc: bool, input_amount: u64) -> Result<u64> {
+        require!(input_amount > 0, ErrorCode::InvalidAmount);
+        let (input_reserve, output_reserve) = if is_token_to_usdc {
+            (&mut self.token_reserve, &mut self.usdc_reserve)
+        } else {
+            (&mut self.usdc_reserve, &mut self.token_reserve)
+        };
+        let output_amount = quote_swap(*input_reserve, *output_reserve, input_amount, self.swap_fee_bps)?;
+        *input_reserve = input_reserve
+            .checked_add(input_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        *output_reserve = output_reserve
+            .checked_sub(output_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(output_amount)
+    }
 }
 
 #[error_code]
@@ -437,4 +796,19 @@ pub enum ErrorCode {
     
     #[msg("Slippage too high")]
     SlippageTooHigh,
+    
+    #[msg("Pool has insufficient liquidity")]
+    InsufficientLiquidity,
+    
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Swap fee too high")]
+    FeeTooHigh,
+    
+    #[msg("Pool id too long")]
+    PoolIdTooLong,
+    
+    #[msg("Account is not the Meteora swap program or one of its pools")]
+    InvalidSwapProgram,
 }
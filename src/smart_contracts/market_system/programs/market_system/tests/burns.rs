@@ -0,0 +1,33 @@
+use anchor_lang::AccountDeserialize;
+use market_system::collateral::CollateralConfig;
+use market_system::security_checks;
+use market_system::{ErrorCode, Market};
+
+#[test]
+fn burn_sequence_starts_at_one_and_never_repeats() {
+    let mut config = CollateralConfig::default();
+    assert_eq!(config.next_burn_sequence().unwrap(), 1);
+    assert_eq!(config.next_burn_sequence().unwrap(), 2);
+    assert_eq!(config.burn_sequence, 2);
+
+    // Wrapping around would hand out sequence 0 again
+    config.burn_sequence = u64::MAX;
+    assert_eq!(
+        config.next_burn_sequence().unwrap_err(),
+        security_checks::ErrorCode::CalculationError.into()
+    );
+    assert_eq!(config.burn_sequence, u64::MAX);
+}
+
+#[test]
+fn burn_events_name_the_option_without_padding() {
+    let data = [0u8; 8 + Market::LEN];
+    let mut market = Market::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    market.option_1[..3].copy_from_slice(b"Yes");
+    market.option_4 = [b'x'; 50];
+
+    assert_eq!(market.option_name(0).unwrap(), "Yes");
+    assert_eq!(market.option_name(1).unwrap(), "");
+    assert_eq!(market.option_name(3).unwrap().len(), 50);
+    assert_eq!(market.option_name(4).unwrap_err(), ErrorCode::InvalidOptionIndex.into());
+}
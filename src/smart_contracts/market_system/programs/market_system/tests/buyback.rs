@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use market_system::{BuybackPool, PlatformState};
+
+fn pinned(input_mint: Pubkey, min_price: u64) -> BuybackPool {
+    BuybackPool { input_mint, pool: Pubkey::new_unique(), min_price }
+}
+
+#[test]
+fn minimum_output_follows_the_pinned_price() {
+    // At least 0.98 platform tokens (6 decimals) per input token
+    let pool = pinned(Pubkey::new_unique(), 980_000);
+    assert_eq!(pool.min_output(1_000_000, 6).unwrap(), 980_000);
+    assert_eq!(pool.min_output(2_500_000, 6).unwrap(), 2_450_000);
+    // A 9-decimal input mint (e.g. wrapped SOL) scales the same price
+    assert_eq!(pool.min_output(1_000_000_000, 9).unwrap(), 980_000);
+}
+
+#[test]
+fn minimum_output_rounds_up() {
+    let pool = pinned(Pubkey::new_unique(), 980_000);
+    // 0.98 of a single base unit still needs one token out
+    assert_eq!(pool.min_output(1, 6).unwrap(), 1);
+    assert_eq!(pool.min_output(0, 6).unwrap(), 0);
+    assert!(pinned(Pubkey::new_unique(), u64::MAX).min_output(u64::MAX, 0).is_err());
+}
+
+#[test]
+fn pools_are_looked_up_by_input_mint() {
+    let data = [0u8; 8 + PlatformState::LEN];
+    let mut state = PlatformState::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    let (usdc, wsol) = (Pubkey::new_unique(), Pubkey::new_unique());
+    state.buyback_pools = vec![pinned(usdc, 980_000), pinned(wsol, 150_000_000)];
+
+    assert_eq!(state.buyback_pool_for(&usdc), Some(&state.buyback_pools[0]));
+    assert_eq!(state.buyback_pool_for(&wsol).unwrap().min_price, 150_000_000);
+    assert!(state.buyback_pool_for(&Pubkey::new_unique()).is_none());
+
+    // A full pool list still fits the account
+    state.buyback_pools = (0..BuybackPool::MAX_POOLS).map(|_| pinned(Pubkey::new_unique(), 1)).collect();
+    let mut buffer = Vec::new();
+    state.try_serialize(&mut buffer).unwrap();
+    assert!(buffer.len() <= 8 + PlatformState::LEN);
+}
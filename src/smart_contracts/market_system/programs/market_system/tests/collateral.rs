@@ -0,0 +1,57 @@
+use market_system::collateral::CollateralConfig;
+
+#[test]
+fn mint_settings_cap_the_fee_and_switch_burns() {
+    let mut config = CollateralConfig {
+        burn_enabled: true,
+        platform_fee_rate: 50,
+        ..Default::default()
+    };
+    assert_eq!(config.bet_rates(100, 250), (50, 250));
+    assert_eq!(config.bet_rates(25, 250), (25, 250));
+    assert_eq!(config.burn_rate(150), 150);
+
+    config.burn_enabled = false;
+    assert_eq!(config.bet_rates(100, 250), (50, 0));
+    assert_eq!(config.burn_rate(150), 0);
+}
+
+#[test]
+fn activity_accumulates_per_mint() {
+    let mut config = CollateralConfig::default();
+    config.record_activity(5_000, 125).unwrap();
+    config.record_activity(1_000, 25).unwrap();
+    assert_eq!(config.total_volume, 6_000);
+    assert_eq!(config.total_burned, 150);
+
+    config.total_volume = u64::MAX;
+    assert!(config.record_activity(1, 0).is_err());
+}
+
+#[test]
+fn limits_are_whole_tokens_of_the_mint() {
+    let config = CollateralConfig {
+        max_bet_tokens: 1_000,
+        max_pool_tokens: 100_000,
+        ..Default::default()
+    };
+    assert_eq!(config.max_bet_amount(6).unwrap(), 1_000_000_000);
+    assert_eq!(config.max_bet_amount(9).unwrap(), 1_000_000_000_000);
+    assert_eq!(config.max_pool_amount(0).unwrap(), 100_000);
+    assert!(config.max_pool_amount(18).is_err());
+}
+
+#[test]
+fn limits_scale_whole_tokens_by_decimals() {
+    let config = CollateralConfig {
+        min_bet_tokens: 2,
+        max_bet_tokens: 1_000,
+        max_pool_tokens: 50_000,
+        ..Default::default()
+    };
+    assert_eq!(config.min_bet_amount(6).unwrap(), 2_000_000);
+    assert_eq!(config.min_bet_amount(9).unwrap(), 2_000_000_000);
+    assert_eq!(config.max_bet_amount(0).unwrap(), 1_000);
+    assert_eq!(config.max_pool_amount(9).unwrap(), 50_000_000_000_000);
+    assert!(config.max_pool_amount(19).is_err());
+}
@@ -0,0 +1,64 @@
+use market_system::access_control::CreatorConfig;
+use market_system::ErrorCode;
+
+fn category(name: &str) -> [u8; 20] {
+    let mut bytes = [0u8; 20];
+    bytes[..name.len()].copy_from_slice(name.as_bytes());
+    bytes
+}
+
+fn config() -> CreatorConfig {
+    CreatorConfig {
+        max_fee_rate: 200,
+        max_active_markets: 2,
+        min_stake_tokens: 10,
+        allowed_categories: vec![category("sports")],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn markets_within_the_limits_take_a_slot() {
+    let mut config = config();
+    // Ten whole tokens of a 6 decimal mint
+    config.open_market(200, 10_000_000, &category("sports"), 6).unwrap();
+    assert_eq!(config.active_markets, 1);
+}
+
+#[test]
+fn fee_above_the_cap_is_refused() {
+    let err = config().open_market(201, 10_000_000, &category("sports"), 6).unwrap_err();
+    assert_eq!(err, ErrorCode::FeeTooHigh.into());
+}
+
+#[test]
+fn minimum_stake_is_whole_tokens() {
+    let err = config().open_market(200, 9_999_999, &category("sports"), 6).unwrap_err();
+    assert_eq!(err, ErrorCode::CreatorStakeTooLow.into());
+    let err = config().open_market(200, 9_999_999_999, &category("sports"), 9).unwrap_err();
+    assert_eq!(err, ErrorCode::CreatorStakeTooLow.into());
+    assert!(config().open_market(200, 10_000_000_000, &category("sports"), 9).is_ok());
+}
+
+#[test]
+fn disallowed_categories_are_refused() {
+    let err = config().open_market(200, 10_000_000, &category("politics"), 6).unwrap_err();
+    assert_eq!(err, ErrorCode::CategoryNotAllowed.into());
+
+    let open = CreatorConfig { allowed_categories: Vec::new(), ..config() };
+    assert!(open.allows_category(&category("politics")));
+}
+
+#[test]
+fn creators_at_quota_wait_for_a_market_to_close() {
+    let mut config = config();
+    config.open_market(200, 10_000_000, &category("sports"), 6).unwrap();
+    config.open_market(200, 10_000_000, &category("sports"), 6).unwrap();
+    let err = config.open_market(200, 10_000_000, &category("sports"), 6).unwrap_err();
+    assert_eq!(err, ErrorCode::ActiveMarketLimitReached.into());
+    assert_eq!(config.active_markets, 2);
+
+    config.close_market();
+    assert_eq!(config.active_markets, 1);
+    config.open_market(200, 10_000_000, &category("sports"), 6).unwrap();
+}
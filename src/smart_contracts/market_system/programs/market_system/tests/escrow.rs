@@ -0,0 +1,81 @@
+use anchor_lang::AccountDeserialize;
+use market_system::security_checks::ErrorCode;
+use market_system::{Market, MarketStatus};
+
+fn blank_market() -> Market {
+    let data = [0u8; 8 + Market::LEN];
+    Market::try_deserialize_unchecked(&mut &data[..]).unwrap()
+}
+
+#[test]
+fn live_markets_owe_the_whole_pool_and_bond() {
+    let mut market = blank_market();
+    market.status = MarketStatus::Active;
+    market.total_pool = 1_000;
+    market.creator_bond = 300;
+    assert_eq!(market.escrow_liability().unwrap(), 1_300);
+
+    // Claims can't have happened yet, so a stray counter is not subtracted
+    market.status = MarketStatus::Paused;
+    market.total_claimed = 400;
+    assert_eq!(market.escrow_liability().unwrap(), 1_300);
+}
+
+#[test]
+fn claims_and_refunds_reduce_what_is_owed() {
+    let mut resolved = blank_market();
+    resolved.status = MarketStatus::Resolved;
+    resolved.total_pool = 1_000;
+    resolved.total_claimed = 600;
+    resolved.total_refunded = 1_000;
+    assert_eq!(resolved.escrow_liability().unwrap(), 400);
+
+    let mut cancelled = blank_market();
+    cancelled.status = MarketStatus::Cancelled;
+    cancelled.total_pool = 1_000;
+    cancelled.total_claimed = 1_000;
+    cancelled.total_refunded = 250;
+    assert_eq!(cancelled.escrow_liability().unwrap(), 750);
+}
+
+#[test]
+fn paying_out_more_than_the_pool_is_an_error() {
+    let mut market = blank_market();
+    market.status = MarketStatus::Resolved;
+    market.total_pool = 100;
+    market.total_claimed = 101;
+    assert_eq!(market.escrow_liability().unwrap_err(), ErrorCode::CalculationError.into());
+}
+
+#[test]
+fn liability_includes_whitelisted_stake() {
+    let mut market = blank_market();
+    market.total_pool = 1_000;
+    market.creator_stake = 250;
+    assert_eq!(market.escrow_liability().unwrap(), 1_250);
+
+    // A mis-sent deposit on top is the only recoverable amount
+    let held = 1_250 + 40;
+    assert_eq!(held - market.escrow_liability().unwrap(), 40);
+}
+
+#[test]
+fn liability_keeps_stake_after_payouts_until_released() {
+    let mut market = blank_market();
+    market.total_pool = 1_000;
+    market.creator_stake = 250;
+    market.status = MarketStatus::Resolved;
+    market.total_claimed = 1_000;
+    assert_eq!(market.escrow_liability().unwrap(), 250);
+
+    market.creator_stake = 0;
+    assert_eq!(market.escrow_liability().unwrap(), 0);
+}
+
+#[test]
+fn liability_counts_bond_and_stake_separately() {
+    let mut market = blank_market();
+    market.creator_bond = 100;
+    market.creator_stake = 50;
+    assert_eq!(market.escrow_liability().unwrap(), 150);
+}
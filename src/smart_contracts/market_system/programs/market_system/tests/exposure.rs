@@ -0,0 +1,67 @@
+use anchor_lang::AccountDeserialize;
+use market_system::{ErrorCode, Market};
+
+fn capped_market(max_user_stake: u64, max_option_share_bps: u16) -> Market {
+    let data = [0u8; 8 + Market::LEN];
+    let mut market = Market::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    market.option_count = 2;
+    market.min_bet_amount = 1_000;
+    market.max_user_stake = max_user_stake;
+    market.max_option_share_bps = max_option_share_bps;
+    market
+}
+
+#[test]
+fn uncapped_markets_take_any_position() {
+    let mut market = capped_market(0, 0);
+    market.option_1_pool = u64::MAX;
+    assert!(market.validate_exposure(0, u64::MAX, 1).is_ok());
+}
+
+#[test]
+fn the_first_stake_on_an_empty_option_is_exempt_from_the_share_cap() {
+    let mut market = capped_market(0, 2_500);
+    market.option_1_pool = 50_000;
+    assert!(market.validate_exposure(0, 50_000, 50_000).is_ok());
+
+    // Sponsor seed alone doesn't count as a stake
+    market.subsidy_seed[0] = 10_000;
+    market.option_1_pool = 60_000;
+    assert!(market.validate_exposure(0, 50_000, 50_000).is_ok());
+}
+
+#[test]
+fn a_sole_bettors_second_bet_is_capped() {
+    let mut market = capped_market(0, 2_500);
+    market.option_1_pool = 2_000;
+    let err = market.validate_exposure(0, 2_000, 1_000).unwrap_err();
+    assert_eq!(err, ErrorCode::OptionShareExceeded.into());
+
+    // Also when the option was seeded
+    market.subsidy_seed[0] = 10_000;
+    market.option_1_pool = 12_000;
+    let err = market.validate_exposure(0, 4_000, 1_000).unwrap_err();
+    assert_eq!(err, ErrorCode::OptionShareExceeded.into());
+}
+
+#[test]
+fn share_cap_applies_to_positions_in_a_shared_pool() {
+    let mut market = capped_market(0, 2_500);
+    market.option_2_pool = 10_000;
+    assert!(market.validate_exposure(1, 2_500, 2_500).is_ok());
+    let err = market.validate_exposure(1, 2_501, 2_501).unwrap_err();
+    assert_eq!(err, ErrorCode::OptionShareExceeded.into());
+    // At the market minimum but most of a pool someone else seeded
+    market.option_2_pool = 1_200;
+    let err = market.validate_exposure(1, 1_000, 1_000).unwrap_err();
+    assert_eq!(err, ErrorCode::OptionShareExceeded.into());
+}
+
+#[test]
+fn user_stake_cap_applies_to_every_position() {
+    let mut market = capped_market(10_000, 0);
+    market.option_1_pool = 10_001;
+    assert!(market.validate_exposure(0, 10_000, 10_000).is_ok());
+    let err = market.validate_exposure(0, 10_001, 10_001).unwrap_err();
+    assert_eq!(err, ErrorCode::PositionLimitExceeded.into());
+}
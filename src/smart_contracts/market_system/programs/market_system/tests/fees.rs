@@ -0,0 +1,180 @@
+use anchor_lang::AccountDeserialize;
+use market_system::collateral::CollateralConfig;
+use market_system::fees::{FeeLedger, FeeRecipient, FeeShares, FeeSource, FeeSplit, FeeTier, PointsTier};
+use market_system::referral::ReferrerRewards;
+use market_system::{ErrorCode, Market, PlatformState};
+
+fn blank_market() -> Market {
+    let data = [0u8; 8 + Market::LEN];
+    Market::try_deserialize_unchecked(&mut &data[..]).unwrap()
+}
+
+fn split(recipient: FeeRecipient, bet_bps: u16, claim_bps: u16) -> FeeSplit {
+    FeeSplit { recipient, bet_bps, claim_bps }
+}
+
+fn table() -> Vec<FeeSplit> {
+    vec![
+        split(FeeRecipient::Treasury, 5000, 2000),
+        split(FeeRecipient::Referrer, 2000, 0),
+        split(FeeRecipient::Stakers, 3000, 0),
+        split(FeeRecipient::Creator, 0, 8000),
+    ]
+}
+
+#[test]
+fn split_sends_rounding_dust_to_the_treasury() {
+    FeeSplit::validate(&table()).unwrap();
+    let shares = FeeShares::split(1_001, &table(), FeeSource::Bet).unwrap();
+    assert_eq!(shares.referrer, 200);
+    assert_eq!(shares.stakers, 300);
+    assert_eq!(shares.creator, 0);
+    assert_eq!(shares.treasury, 1_001 - 200 - 300);
+
+    let claim = FeeShares::split(1_000, &table(), FeeSource::Claim).unwrap();
+    assert_eq!((claim.creator, claim.treasury, claim.stakers), (800, 200, 0));
+}
+
+#[test]
+fn invalid_tables_are_rejected() {
+    let short = vec![split(FeeRecipient::Treasury, 9999, 10000)];
+    let duplicate = vec![
+        split(FeeRecipient::Treasury, 5000, 5000),
+        split(FeeRecipient::Treasury, 5000, 5000),
+    ];
+    for splits in [vec![], short, duplicate] {
+        assert_eq!(FeeSplit::validate(&splits).unwrap_err(), ErrorCode::InvalidFeeSplit.into());
+    }
+    FeeSplit::validate(&FeeSplit::defaults()).unwrap();
+}
+
+#[test]
+fn unclaimed_referrer_and_sponsor_shares_go_to_the_treasury() {
+    let mut ledger = FeeLedger::default();
+    let mut market = blank_market();
+    market.creator_fees_accrued = 5;
+    let shares = FeeShares { treasury: 50, creator: 10, referrer: 20, stakers: 15, sponsors: 5 };
+    ledger.accrue(&mut market, None, &shares).unwrap();
+    assert_eq!(ledger.treasury_accrued, 75);
+    assert_eq!(ledger.referrers_accrued, 0);
+    assert_eq!(ledger.sponsors_accrued, 0);
+    assert_eq!(ledger.stakers_accrued, 15);
+    assert_eq!(ledger.creators_accrued, 10);
+    assert_eq!(market.creator_fees_accrued, 15);
+}
+
+#[test]
+fn sponsor_share_accrues_to_a_sponsored_market() {
+    let mut ledger = FeeLedger::default();
+    let mut market = blank_market();
+    market.subsidy_bonus = 1_000;
+    market.subsidy_total = 1_000;
+    let shares = FeeShares { treasury: 50, sponsors: 10, ..Default::default() };
+    ledger.accrue(&mut market, None, &shares).unwrap();
+    assert_eq!(ledger.sponsors_accrued, 10);
+    assert_eq!(ledger.treasury_accrued, 50);
+    assert!(market.sponsor_fee_per_share > 0);
+}
+
+#[test]
+fn referrer_share_accrues_to_the_bound_referrer() {
+    let mut ledger = FeeLedger::default();
+    let mut market = blank_market();
+    let mut rewards = ReferrerRewards::default();
+    let shares = FeeShares { treasury: 50, creator: 0, referrer: 20, stakers: 0, sponsors: 0 };
+    ledger.accrue(&mut market, Some(&mut rewards), &shares).unwrap();
+    assert_eq!((rewards.accrued, rewards.total_earned), (20, 20));
+    assert_eq!(ledger.referrers_accrued, 20);
+    assert_eq!(ledger.treasury_accrued, 50);
+}
+
+fn tier(min_volume: u64, platform_fee_rate: u64, bet_burn_rate: u64) -> FeeTier {
+    FeeTier { min_volume, platform_fee_rate, bet_burn_rate }
+}
+
+#[test]
+fn bet_rates_follow_the_highest_tier_reached() {
+    let data = [0u8; 8 + PlatformState::LEN];
+    let mut state = PlatformState::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    state.platform_fee_rate = 100;
+    state.bet_burn_rate = 250;
+    state.fee_tiers = vec![tier(1_000, 50, 200), tier(5_000, 25, 100)];
+
+    assert_eq!(state.bet_rates_for(0), (100, 250));
+    assert_eq!(state.bet_rates_for(999), (100, 250));
+    assert_eq!(state.bet_rates_for(1_000), (50, 200));
+    assert_eq!(state.bet_rates_for(4_999), (50, 200));
+    assert_eq!(state.bet_rates_for(u64::MAX), (25, 100));
+}
+
+#[test]
+fn fee_tiers_must_rise_and_only_discount() {
+    FeeTier::validate(&[], 100, 250).unwrap();
+    FeeTier::validate(&[tier(1_000, 50, 200), tier(5_000, 50, 100)], 100, 250).unwrap();
+
+    let invalid = [
+        vec![tier(1_000, 101, 250)],
+        vec![tier(1_000, 100, 251)],
+        vec![tier(1_000, 50, 200), tier(1_000, 25, 100)],
+        vec![tier(1_000, 50, 200), tier(5_000, 75, 100)],
+        (1..=6).map(|volume| tier(volume, 0, 0)).collect(),
+    ];
+    for tiers in invalid {
+        assert_eq!(FeeTier::validate(&tiers, 100, 250).unwrap_err(), ErrorCode::InvalidFeeTier.into());
+    }
+}
+
+fn points(min_balance: u64, discount_bps: u16) -> PointsTier {
+    PointsTier { min_balance, discount_bps }
+}
+
+fn tiered_state() -> PlatformState {
+    let data = [0u8; 8 + PlatformState::LEN];
+    let mut state = PlatformState::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    state.platform_fee_rate = 100;
+    state.bet_burn_rate = 250;
+    state.fee_tiers = vec![tier(1_000, 60, 100)];
+    state.points_tiers = vec![points(100, 2_500), points(1_000, 5_000)];
+    state
+}
+
+#[test]
+fn points_discounts_waive_part_of_the_fee_rate() {
+    let state = tiered_state();
+    assert_eq!(state.points_discount_for(99), 0);
+    assert_eq!(state.points_discount_for(100), 2_500);
+    assert_eq!(state.points_discount_for(u64::MAX), 5_000);
+
+    assert_eq!(PointsTier::discounted_rate(100, 2_500).unwrap(), 75);
+    // 12.5bp rounds down
+    assert_eq!(PointsTier::discounted_rate(25, 5_000).unwrap(), 12);
+    assert_eq!(PointsTier::discounted_rate(100, 10_000).unwrap(), 0);
+    assert!(PointsTier::discounted_rate(100, 10_001).is_err());
+}
+
+#[test]
+fn discount_applies_after_the_volume_tier_and_collateral_cap() {
+    let state = tiered_state();
+    let collateral = CollateralConfig { burn_enabled: true, platform_fee_rate: 80, ..Default::default() };
+
+    // Base 100bp, capped at 80 by the collateral, then half off
+    assert_eq!(state.bet_rates(0, &collateral, 5_000).unwrap(), (40, 250));
+    // Tier 60bp is under the cap, then a quarter off
+    assert_eq!(state.bet_rates(1_000, &collateral, 2_500).unwrap(), (45, 100));
+    // The burn is never discounted and stops with the collateral's burns
+    let no_burn = CollateralConfig { burn_enabled: false, ..collateral };
+    assert_eq!(state.bet_rates(1_000, &no_burn, 10_000).unwrap(), (0, 0));
+}
+
+#[test]
+fn points_tiers_must_rise_without_shrinking_the_discount() {
+    PointsTier::validate(&[points(100, 2_500), points(1_000, 5_000)]).unwrap();
+    let invalid = [
+        vec![points(100, 10_001)],
+        vec![points(100, 2_500), points(100, 5_000)],
+        vec![points(100, 5_000), points(1_000, 2_500)],
+    ];
+    for tiers in invalid {
+        assert_eq!(PointsTier::validate(&tiers).unwrap_err(), ErrorCode::InvalidPointsTier.into());
+    }
+}
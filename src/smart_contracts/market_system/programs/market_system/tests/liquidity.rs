@@ -0,0 +1,78 @@
+use anchor_lang::prelude::Pubkey;
+use market_system::utils::meteora_integration::{ErrorCode, LiquidityPoolState, MINIMUM_LIQUIDITY};
+
+fn empty_pool(swap_fee_bps: u16) -> LiquidityPoolState {
+    LiquidityPoolState {
+        authority: Pubkey::default(),
+        token_mint: Pubkey::default(),
+        usdc_mint: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+        pool_id: String::new(),
+        is_active: true,
+        swap_fee_bps,
+        token_reserve: 0,
+        usdc_reserve: 0,
+        lp_supply: 0,
+    }
+}
+
+#[test]
+fn first_deposit_locks_minimum_liquidity() {
+    let mut pool = empty_pool(30);
+    let (lp_amount, token_amount, usdc_amount) = pool.deposit(1_000_000, 4_000_000).unwrap();
+    assert_eq!(lp_amount, 2_000_000 - MINIMUM_LIQUIDITY);
+    assert_eq!((token_amount, usdc_amount), (1_000_000, 4_000_000));
+    assert_eq!(pool.lp_supply, 2_000_000);
+
+    // A dust first deposit can't mint past the locked units
+    let err = empty_pool(30).deposit(1_000, 1_000).unwrap_err();
+    assert_eq!(err, ErrorCode::InsufficientLiquidity.into());
+}
+
+#[test]
+fn later_deposits_are_taken_in_the_reserve_ratio() {
+    let mut pool = empty_pool(30);
+    pool.deposit(1_000_000, 4_000_000).unwrap();
+
+    // Surplus USDC over the 1:4 ratio is left with the provider
+    let (lp_amount, token_amount, usdc_amount) = pool.deposit(500_000, 3_000_000).unwrap();
+    assert_eq!(lp_amount, 1_000_000);
+    assert_eq!((token_amount, usdc_amount), (500_000, 2_000_000));
+    assert_eq!((pool.token_reserve, pool.usdc_reserve), (1_500_000, 6_000_000));
+}
+
+#[test]
+fn withdrawals_redeem_pro_rata_down_to_the_floor() {
+    let mut pool = empty_pool(30);
+    let (lp_amount, _, _) = pool.deposit(1_000_000, 4_000_000).unwrap();
+
+    assert_eq!(pool.withdraw(1_000_000).unwrap(), (500_000, 2_000_000));
+    assert_eq!(pool.lp_supply, 1_000_000);
+
+    // The locked units can never be redeemed
+    let remaining = lp_amount - 1_000_000;
+    let err = pool.withdraw(remaining + 1).unwrap_err();
+    assert_eq!(err, ErrorCode::InsufficientLiquidity.into());
+    assert_eq!(pool.withdraw(0).unwrap_err(), ErrorCode::InvalidAmount.into());
+}
+
+#[test]
+fn swaps_book_the_whole_input_into_the_reserves() {
+    let mut pool = empty_pool(30);
+    pool.deposit(1_000_000, 4_000_000).unwrap();
+
+    let output_amount = pool.apply_swap(true, 100_000).unwrap();
+    assert_eq!(output_amount, 362_644);
+    assert_eq!((pool.token_reserve, pool.usdc_reserve), (1_100_000, 4_000_000 - 362_644));
+    assert!(pool.token_reserve as u128 * pool.usdc_reserve as u128 > 4_000_000_000_000);
+}
+
+#[test]
+fn swaps_need_an_input_and_a_funded_pool() {
+    let mut pool = empty_pool(30);
+    assert_eq!(pool.apply_swap(false, 1_000).unwrap_err(), ErrorCode::InsufficientLiquidity.into());
+
+    pool.deposit(1_000_000, 4_000_000).unwrap();
+    assert_eq!(pool.apply_swap(false, 0).unwrap_err(), ErrorCode::InvalidAmount.into());
+    assert_eq!((pool.token_reserve, pool.usdc_reserve), (1_000_000, 4_000_000));
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator};
+use market_system::migration::{legacy_data, LegacyMarket, LegacyPlatformState};
+use market_system::{ErrorCode, Market, MarketStatus, MarketType, PlatformState};
+
+fn legacy_platform_state(is_paused: bool) -> LegacyPlatformState {
+    LegacyPlatformState {
+        authority: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        bet_burn_rate: 150,
+        claim_burn_rate: 100,
+        platform_fee_rate: 250,
+        total_burned: 7,
+        total_volume: 9,
+        is_paused,
+    }
+}
+
+fn legacy_market() -> LegacyMarket {
+    LegacyMarket {
+        authority: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        market_type: MarketType::Binary,
+        question: [1; 200],
+        question_len: 200,
+        option_1: [2; 50],
+        option_2: [3; 50],
+        option_3: [0; 50],
+        option_4: [0; 50],
+        option_count: 2,
+        resolution_date: 1_700_000_000,
+        creator_fee_rate: 100,
+        min_bet_amount: 1_000_000,
+        token_mint: Pubkey::new_unique(),
+        status: MarketStatus::Resolved,
+        winning_option: Some(1),
+        option_1_pool: 4_000,
+        option_2_pool: 6_000,
+        option_3_pool: 0,
+        option_4_pool: 0,
+        total_pool: 10_000,
+        market_id: [4; 32],
+        category: [5; 20],
+    }
+}
+
+/// Account bytes as the first deployment allocated them
+fn legacy_account<T: AnchorSerialize>(discriminator: &[u8], legacy_len: usize, value: &T) -> Vec<u8> {
+    let mut data = vec![0u8; 8 + legacy_len];
+    data[..8].copy_from_slice(discriminator);
+    let body = value.try_to_vec().unwrap();
+    data[8..8 + body.len()].copy_from_slice(&body);
+    data
+}
+
+fn with_account<R>(data: &mut [u8], f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let owner = market_system::ID;
+    let mut lamports = 0;
+    let account = AccountInfo::new(&key, false, true, &mut lamports, data, &owner, false, 0);
+    f(&account)
+}
+
+#[test]
+fn legacy_platform_state_gets_the_current_defaults() {
+    let legacy = legacy_platform_state(true);
+    let authority = legacy.authority;
+    let mut data = legacy_account(PlatformState::DISCRIMINATOR, LegacyPlatformState::LEN, &legacy);
+    assert_eq!(data.len(), 145);
+
+    let body = with_account(&mut data, |account| {
+        legacy_data::<PlatformState>(account, LegacyPlatformState::LEN).unwrap()
+    });
+    let migrated = LegacyPlatformState::deserialize(&mut &body[..]).unwrap().migrate();
+    assert_eq!(migrated.authority, authority);
+    assert_eq!(migrated.platform_fee_rate, 250);
+    // The old flag only stopped creation and betting
+    assert_eq!(migrated.pause_flags, PlatformState::PAUSE_CREATION | PlatformState::PAUSE_BETTING);
+    assert!(!migrated.is_paused(PlatformState::PAUSE_RESOLUTION));
+    assert!(!migrated.is_paused(PlatformState::PAUSE_PAYOUTS));
+    assert!(migrated.buyback_pools.is_empty());
+    assert!(!migrated.fee_splits.is_empty());
+
+    // The rewritten account loads as the current type
+    let mut account = vec![0u8; 8 + PlatformState::LEN];
+    migrated.try_serialize(&mut &mut account[..]).unwrap();
+    let loaded = PlatformState::try_deserialize(&mut &account[..]).unwrap();
+    assert_eq!(loaded.total_volume, 9);
+    assert_eq!(legacy_platform_state(false).migrate().pause_flags, 0);
+}
+
+#[test]
+fn legacy_market_keeps_its_pools_and_outcome() {
+    let mut data = legacy_account(Market::DISCRIMINATOR, LegacyMarket::LEN, &legacy_market());
+    assert_eq!(data.len(), 8 + 683);
+
+    let body = with_account(&mut data, |account| {
+        legacy_data::<Market>(account, LegacyMarket::LEN).unwrap()
+    });
+    // Nothing claimed yet: the escrow still holds the whole pool
+    let migrated = LegacyMarket::deserialize(&mut &body[..]).unwrap().migrate(10_000);
+
+    let mut account = vec![0u8; 8 + Market::LEN];
+    migrated.try_serialize(&mut &mut account[..]).unwrap();
+    let loaded = Market::try_deserialize(&mut &account[..]).unwrap();
+    assert_eq!(loaded.status, MarketStatus::Resolved);
+    assert_eq!(loaded.winning_option, Some(1));
+    assert_eq!((loaded.option_2_pool, loaded.total_pool), (6_000, 10_000));
+    assert_eq!((loaded.creator_stake, loaded.subsidy_total), (0, 0));
+    assert_eq!((loaded.total_claimed, loaded.escrow_liability().unwrap()), (0, 10_000));
+}
+
+#[test]
+fn legacy_payouts_are_derived_from_the_escrow() {
+    // 7_500 of the pool was claimed before the migration
+    let resolved = legacy_market().migrate(2_500);
+    assert_eq!((resolved.total_claimed, resolved.total_refunded), (7_500, 0));
+    assert_eq!(resolved.escrow_liability().unwrap(), 2_500);
+
+    // A surplus in the escrow isn't counted as owed
+    assert_eq!(legacy_market().migrate(12_000).escrow_liability().unwrap(), 10_000);
+
+    let cancelled = LegacyMarket { status: MarketStatus::Cancelled, winning_option: None, ..legacy_market() }.migrate(4_000);
+    assert_eq!((cancelled.total_claimed, cancelled.total_refunded), (0, 6_000));
+    assert_eq!(cancelled.escrow_liability().unwrap(), 4_000);
+
+    // Open markets have paid nothing out
+    let active = LegacyMarket { status: MarketStatus::Active, winning_option: None, ..legacy_market() }.migrate(10_000);
+    assert_eq!((active.total_claimed, active.total_refunded), (0, 0));
+}
+
+#[test]
+fn only_legacy_accounts_of_the_expected_type_migrate() {
+    // A market can't be passed off as the platform state
+    let mut market = legacy_account(Market::DISCRIMINATOR, LegacyPlatformState::LEN, &legacy_platform_state(false));
+    let err = with_account(&mut market, |account| {
+        legacy_data::<PlatformState>(account, LegacyPlatformState::LEN).unwrap_err()
+    });
+    assert_eq!(err, ErrorCode::InvalidAccountLayout.into());
+
+    // Accounts already at the current size are left alone
+    let mut current = vec![0u8; 8 + Market::LEN];
+    current[..8].copy_from_slice(Market::DISCRIMINATOR);
+    let err = with_account(&mut current, |account| {
+        legacy_data::<Market>(account, LegacyMarket::LEN).unwrap_err()
+    });
+    assert_eq!(err, ErrorCode::AccountAlreadyMigrated.into());
+}
+
+#[test]
+fn migrations_fit_in_one_realloc() {
+    const { assert!(PlatformState::LEN - LegacyPlatformState::LEN <= MAX_PERMITTED_DATA_INCREASE) };
+    const { assert!(Market::LEN - LegacyMarket::LEN <= MAX_PERMITTED_DATA_INCREASE) };
+}
@@ -0,0 +1,144 @@
+use anchor_lang::AccountDeserialize;
+use market_system::parlay::{LegResult, Parlay, ParlayHouse, ParlayLeg};
+use market_system::{ErrorCode, Market, MarketStatus};
+
+fn two_option_market(option_1_pool: u64, option_2_pool: u64) -> Market {
+    let data = [0u8; 8 + Market::LEN];
+    let mut market = Market::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    market.option_count = 2;
+    market.option_1_pool = option_1_pool;
+    market.option_2_pool = option_2_pool;
+    market.total_pool = option_1_pool + option_2_pool;
+    market
+}
+
+fn leg(odds_bps: u64, result: LegResult) -> ParlayLeg {
+    ParlayLeg { market: Default::default(), option_index: 0, odds_bps, result }
+}
+
+#[test]
+fn leg_odds_are_the_pool_over_the_option_stake() {
+    let market = two_option_market(100, 200);
+    assert_eq!(ParlayLeg::odds_for(&market, 0, 0, 1).unwrap(), 30_000);
+    assert_eq!(ParlayLeg::odds_for(&market, 1, 0, 1).unwrap(), 15_000);
+
+    let empty = two_option_market(0, 200);
+    assert_eq!(ParlayLeg::odds_for(&empty, 0, 0, 1).unwrap_err(), ErrorCode::ParlayOddsUnavailable.into());
+}
+
+#[test]
+fn leg_odds_are_net_of_claim_fees_and_leave_out_the_bonus() {
+    let mut market = two_option_market(100_000, 200_000);
+    market.creator_fee_rate = 200;
+    market.subsidy_bonus = 300_000;
+    // 300_000 less 2% creator fee and 3% claim burn, over 100_000
+    assert_eq!(ParlayLeg::odds_for(&market, 0, 300, 1).unwrap(), 28_500);
+}
+
+#[test]
+fn same_slot_pool_swings_cannot_raise_leg_odds() {
+    let mut market = two_option_market(100_000, 200_000);
+    // A bet on the other side in slot 5, read in the same slot
+    market.record_pool_change(5);
+    market.option_2_pool += 700_000;
+    market.total_pool += 700_000;
+    assert_eq!(ParlayLeg::odds_for(&market, 0, 0, 5).unwrap(), 30_000);
+
+    // A later bet in the same slot doesn't move the settled pools either
+    market.record_pool_change(5);
+    market.option_1_pool += 100_000;
+    market.total_pool += 100_000;
+    assert_eq!(market.settled_option_pools(5), [100_000, 200_000, 0, 0]);
+    assert_eq!(ParlayLeg::odds_for(&market, 0, 0, 5).unwrap(), 30_000);
+    // A swing that lowers the odds counts straight away
+    assert_eq!(ParlayLeg::odds_for(&market, 1, 0, 5).unwrap(), 12_222);
+
+    // From the next slot on the swing is priced in
+    assert_eq!(ParlayLeg::odds_for(&market, 0, 0, 6).unwrap(), 55_000);
+}
+
+#[test]
+fn legs_stay_pending_until_the_market_is_final() {
+    let mut market = two_option_market(100, 100);
+    assert_eq!(leg(20_000, LegResult::Pending).result_for(&market), None);
+
+    market.status = MarketStatus::Resolved;
+    market.winning_option = Some(0);
+    assert_eq!(leg(20_000, LegResult::Pending).result_for(&market), Some(LegResult::Won));
+    market.winning_option = Some(1);
+    assert_eq!(leg(20_000, LegResult::Pending).result_for(&market), Some(LegResult::Lost));
+
+    market.status = MarketStatus::Cancelled;
+    assert_eq!(leg(20_000, LegResult::Pending).result_for(&market), Some(LegResult::Void));
+}
+
+#[test]
+fn payout_multiplies_counted_legs_less_the_edge() {
+    let legs = [leg(20_000, LegResult::Won), leg(30_000, LegResult::Void), leg(15_000, LegResult::Won)];
+    // 1_000 x 2 x 1.5, less 5%
+    assert_eq!(Parlay::payout_for(1_000, &legs, 500, u64::MAX).unwrap(), 2_850);
+    assert_eq!(Parlay::payout_for(1_000, &legs, 500, 2_000).unwrap(), 2_000);
+
+    let void = [leg(20_000, LegResult::Void), leg(30_000, LegResult::Void)];
+    assert_eq!(Parlay::payout_for(1_000, &void, 500, u64::MAX).unwrap(), 1_000);
+}
+
+#[test]
+fn house_reserves_payouts_against_its_balance() {
+    let mut house = ParlayHouse { balance: 1_000, ..Default::default() };
+    house.book(100, 1_100).unwrap();
+    assert_eq!((house.balance, house.reserved, house.available().unwrap()), (1_100, 1_100, 0));
+    let mut full = house.clone();
+    assert_eq!(full.book(100, 101).unwrap_err(), ErrorCode::InsufficientHouseLiquidity.into());
+
+    house.settle(1_100, 0).unwrap();
+    assert_eq!((house.balance, house.reserved, house.open_parlays), (1_100, 0, 0));
+}
+
+fn house() -> ParlayHouse {
+    ParlayHouse { max_leg_odds_bps: 50_000, min_leg_pool_tokens: 100, ..Default::default() }
+}
+
+#[test]
+fn leg_odds_follow_the_pool_below_the_cap() {
+    // 300 tokens of 6 decimals, a third of it on option 1
+    let market = two_option_market(100_000_000, 200_000_000);
+    assert_eq!(house().leg_odds(&market, 0, 6, 0, 1).unwrap(), 30_000);
+}
+
+#[test]
+fn leg_odds_are_capped() {
+    // Piling onto the other side can't push a leg past the cap
+    let market = two_option_market(1_000_000, 999_000_000);
+    assert_eq!(house().leg_odds(&market, 0, 6, 0, 1).unwrap(), 50_000);
+}
+
+#[test]
+fn thin_leg_markets_are_refused() {
+    let market = two_option_market(40_000_000, 50_000_000);
+    assert_eq!(house().leg_odds(&market, 0, 6, 0, 1).unwrap_err(), ErrorCode::ParlayLegTooThin.into());
+}
+
+#[test]
+fn barely_bet_leg_markets_are_refused() {
+    let mut market = two_option_market(100_000_000, 200_000_000);
+    let house = ParlayHouse { min_leg_bettors: 3, ..house() };
+    market.bettor_count = 2;
+    assert_eq!(house.leg_odds(&market, 0, 6, 0, 1).unwrap_err(), ErrorCode::ParlayLegTooThin.into());
+    market.bettor_count = 3;
+    assert_eq!(house.leg_odds(&market, 0, 6, 0, 1).unwrap(), 30_000);
+}
+
+#[test]
+fn open_legs_block_settlement_until_expiry() {
+    let parlay = Parlay { expires_at: 1_000, ..Default::default() };
+    let mut market = two_option_market(100, 100);
+    let pending = leg(20_000, LegResult::Pending);
+    assert_eq!(parlay.leg_result(&pending, &market, 999).unwrap_err(), ErrorCode::ParlayNotSettleable.into());
+    assert_eq!(parlay.leg_result(&pending, &market, 1_000).unwrap(), LegResult::Void);
+
+    // A final market keeps its result after expiry
+    market.status = MarketStatus::Resolved;
+    market.winning_option = Some(1);
+    assert_eq!(parlay.leg_result(&pending, &market, 2_000).unwrap(), LegResult::Lost);
+}
@@ -0,0 +1,61 @@
+use market_system::security_checks::*;
+
+#[test]
+fn option_index_bounds() {
+    assert!(validate_option_index(1, 2).is_ok());
+    let err = validate_option_index(2, 2).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidOptionIndex.into());
+}
+
+#[test]
+fn bet_amount_limits() {
+    assert!(validate_bet_amount(1_000, 1_000, 5_000).is_ok());
+    assert_eq!(validate_bet_amount(999, 1_000, 5_000).unwrap_err(), ErrorCode::BetTooSmall.into());
+    assert_eq!(validate_bet_amount(5_001, 1_000, 5_000).unwrap_err(), ErrorCode::BetTooLarge.into());
+}
+
+#[test]
+fn pool_overflow_protection() {
+    assert!(validate_pool_addition(9_000, 1_000, 10_000).is_ok());
+    assert_eq!(validate_pool_addition(9_000, 1_001, 10_000).unwrap_err(), ErrorCode::PoolOverflow.into());
+    assert_eq!(
+        validate_pool_addition(u64::MAX, 1, u64::MAX).unwrap_err(),
+        ErrorCode::CalculationError.into()
+    );
+}
+
+#[test]
+fn division_by_zero_protection() {
+    assert_eq!(safe_div(10, 3).unwrap(), 3);
+    assert_eq!(safe_div(10, 0).unwrap_err(), ErrorCode::DivisionByZero.into());
+}
+
+#[test]
+fn arithmetic_overflow_protection() {
+    assert_eq!(safe_mul(u64::MAX, 2).unwrap_err(), ErrorCode::CalculationError.into());
+    assert_eq!(safe_add(u64::MAX, 1).unwrap_err(), ErrorCode::CalculationError.into());
+    assert_eq!(safe_sub(0, 1).unwrap_err(), ErrorCode::CalculationError.into());
+    assert_eq!(scale_to_mint_units(u64::MAX, 9).unwrap_err(), ErrorCode::CalculationError.into());
+    assert_eq!(scale_to_mint_units(5, 6).unwrap(), 5_000_000);
+}
+
+#[test]
+fn fee_rate_limits() {
+    assert!(validate_fee_rate(MAX_FEE_RATE).is_ok());
+    assert_eq!(validate_fee_rate(MAX_FEE_RATE + 1).unwrap_err(), ErrorCode::FeeTooHigh.into());
+}
+
+#[test]
+fn market_duration_limits() {
+    let now = 1_700_000_000;
+    assert!(validate_market_duration(now + MIN_MARKET_DURATION, now).is_ok());
+    assert!(validate_market_duration(now + MAX_MARKET_DURATION, now).is_ok());
+    assert_eq!(
+        validate_market_duration(now + MIN_MARKET_DURATION - 1, now).unwrap_err(),
+        ErrorCode::MarketDurationTooShort.into()
+    );
+    assert_eq!(
+        validate_market_duration(now + MAX_MARKET_DURATION + 1, now).unwrap_err(),
+        ErrorCode::MarketDurationTooLong.into()
+    );
+}
@@ -0,0 +1,71 @@
+use anchor_lang::AccountDeserialize;
+use market_system::sponsorship::Sponsorship;
+use market_system::{ErrorCode, Market, MarketStatus};
+
+fn blank_market() -> Market {
+    let data = [0u8; 8 + Market::LEN];
+    Market::try_deserialize_unchecked(&mut &data[..]).unwrap()
+}
+
+/// Seeds 300 on option 0 plus a 3_700 bonus, then bets 500 and 1_000
+fn sponsored_market() -> Market {
+    let mut market = blank_market();
+    market.option_count = 2;
+    market.credit_option(0, 300).unwrap();
+    market.subsidy_seed[0] = 300;
+    market.subsidy_bonus = 3_700;
+    market.subsidy_total = 4_000;
+    market.credit_option(0, 500).unwrap();
+    market.credit_option(1, 1_000).unwrap();
+    market
+}
+
+#[test]
+fn seed_is_paid_out_but_not_counted_as_stake() {
+    let market = sponsored_market();
+    assert_eq!(market.option_pool(0).unwrap(), 800);
+    assert_eq!(market.option_stake(0).unwrap(), 500);
+    assert_eq!(market.option_stake(1).unwrap(), 1_000);
+    assert_eq!(market.payout_pool().unwrap(), 1_800 + 3_700);
+}
+
+#[test]
+fn sponsor_fees_are_shared_by_subsidy_weight() {
+    let mut market = sponsored_market();
+    let mut seeder = Sponsorship { seed_amount: 300, bonus_amount: 700, ..Default::default() };
+    let mut backer = Sponsorship { bonus_amount: 3_000, ..Default::default() };
+
+    market.accrue_sponsor_fees(400).unwrap();
+    seeder.settle(market.sponsor_fee_per_share).unwrap();
+    backer.settle(market.sponsor_fee_per_share).unwrap();
+    assert_eq!((seeder.fees_owed, backer.fees_owed), (100, 300));
+
+    // Settling again without new fees pays nothing twice
+    seeder.settle(market.sponsor_fee_per_share).unwrap();
+    assert_eq!(seeder.fees_owed, 100);
+}
+
+#[test]
+fn sponsor_fees_need_a_subsidy() {
+    let mut market = blank_market();
+    assert_eq!(market.accrue_sponsor_fees(400).unwrap_err(), ErrorCode::DivisionByZero.into());
+}
+
+#[test]
+fn subsidy_is_only_refundable_when_no_winner_staked() {
+    let mut market = sponsored_market();
+    assert!(!market.subsidy_refundable().unwrap());
+
+    market.status = MarketStatus::Resolved;
+    market.winning_option = Some(1);
+    assert!(!market.subsidy_refundable().unwrap());
+
+    // With only the seed left on option 0, nobody backed the winner
+    market.option_1_pool = 300;
+    market.winning_option = Some(0);
+    assert!(market.subsidy_refundable().unwrap());
+
+    let mut cancelled = sponsored_market();
+    cancelled.status = MarketStatus::Cancelled;
+    assert!(cancelled.subsidy_refundable().unwrap());
+}
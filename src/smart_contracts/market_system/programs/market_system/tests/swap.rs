@@ -0,0 +1,31 @@
+use market_system::utils::meteora_integration::{quote_swap, ErrorCode};
+
+#[test]
+fn quote_keeps_the_constant_product() {
+    let out = quote_swap(100_000_000, 100_000_000, 2_000_000, 0).unwrap();
+    assert_eq!(out, 1_960_784);
+    // The pool never ends up with less than it started with
+    assert!((100_000_000u128 + 2_000_000) * (100_000_000u128 - out as u128) >= 100_000_000u128 * 100_000_000);
+}
+
+#[test]
+fn swap_fee_comes_off_the_input() {
+    // 0.3% of 2 tokens stays in the pool without buying anything
+    assert_eq!(quote_swap(100_000_000, 100_000_000, 2_000_000, 30).unwrap(), 1_955_016);
+    assert_eq!(quote_swap(100_000_000, 100_000_000, 2_000_000, 10000).unwrap(), 0);
+}
+
+#[test]
+fn quote_rounds_against_the_swapper() {
+    // 3 * 3 = 9 over a new input reserve of 4 leaves 2.25, rounded up to 3
+    assert_eq!(quote_swap(3, 3, 1, 0).unwrap(), 0);
+    assert_eq!(quote_swap(10, 10, 0, 0).unwrap(), 0);
+}
+
+#[test]
+fn quote_needs_both_reserves() {
+    assert_eq!(quote_swap(0, 100, 10, 0).unwrap_err(), ErrorCode::InsufficientLiquidity.into());
+    assert_eq!(quote_swap(100, 0, 10, 0).unwrap_err(), ErrorCode::InsufficientLiquidity.into());
+    // Reserves near u64::MAX are computed in u128
+    assert!(quote_swap(u64::MAX, u64::MAX, u64::MAX, 0).is_ok());
+}
@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+
+// Stands in for the Meteora Dynamic AMM on a local validator, where it is
+// loaded at the AMM's program id through Anchor.toml's [[test.genesis]]. Only
+// the swap instruction mirrors Meteora: same discriminator, arguments and
+// account order, and vaults laid out like Meteora's up to `lp_mint`. Each
+// pool is its vaults' only depositor, so its reserves are the vault totals.
+declare_id!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+
+#[program]
+pub mod mock_meteora {
+    use super::*;
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.enabled = 1;
+        vault.bumps = [ctx.bumps.vault, ctx.bumps.token_vault];
+        vault.total_amount = 0;
+        vault.token_vault = ctx.accounts.token_vault.key();
+        vault.fee_vault = Pubkey::default();
+        vault.token_mint = ctx.accounts.token_mint.key();
+        vault.lp_mint = ctx.accounts.lp_mint.key();
+        Ok(())
+    }
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, trade_fee_bps: u16) -> Result<()> {
+        require!(trade_fee_bps < 10000, MockError::InvalidFee);
+        let pool = &mut ctx.accounts.pool;
+        pool.a_vault = ctx.accounts.a_vault.key();
+        pool.b_vault = ctx.accounts.b_vault.key();
+        pool.a_vault_lp = ctx.accounts.a_vault_lp.key();
+        pool.b_vault_lp = ctx.accounts.b_vault_lp.key();
+        pool.trade_fee_bps = trade_fee_bps;
+        Ok(())
+    }
+
+    /// Deposits `amount` of the vault's mint on behalf of the pool, minting
+    /// LP one for one
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor_token.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let vault = &mut ctx.accounts.vault;
+        let mint = vault.token_mint;
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.pool_lp.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[&[b"vault", mint.as_ref(), &[vault.bumps[0]]]],
+            ),
+            amount,
+        )?;
+        vault.total_amount = vault
+            .total_amount
+            .checked_add(amount)
+            .ok_or(MockError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Constant-product swap with the fee taken from the input
+    pub fn swap(ctx: Context<Swap>, in_amount: u64, minimum_out_amount: u64) -> Result<()> {
+        let a_to_b = ctx.accounts.user_source_token.mint == ctx.accounts.a_vault.token_mint;
+        let accounts = &mut *ctx.accounts;
+        let (in_vault, in_token_vault, out_vault, out_token_vault) = if a_to_b {
+            (&mut accounts.a_vault, &accounts.a_token_vault, &mut accounts.b_vault, &accounts.b_token_vault)
+        } else {
+            (&mut accounts.b_vault, &accounts.b_token_vault, &mut accounts.a_vault, &accounts.a_token_vault)
+        };
+        require!(
+            accounts.user_destination_token.mint == out_vault.token_mint,
+            MockError::InvalidMint
+        );
+
+        let in_after_fee = in_amount as u128 * (10000 - accounts.pool.trade_fee_bps as u128) / 10000;
+        let out_amount = u64::try_from(
+            in_after_fee * out_vault.total_amount as u128
+                / (in_vault.total_amount as u128 + in_after_fee),
+        )
+        .map_err(|_| MockError::MathOverflow)?;
+        require!(out_amount >= minimum_out_amount, MockError::ExceededSlippage);
+
+        token::transfer(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: accounts.user_source_token.to_account_info(),
+                    to: in_token_vault.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            in_amount,
+        )?;
+        let out_mint = out_vault.token_mint;
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: out_token_vault.to_account_info(),
+                    to: accounts.user_destination_token.to_account_info(),
+                    authority: out_vault.to_account_info(),
+                },
+                &[&[b"vault", out_mint.as_ref(), &[out_vault.bumps[0]]]],
+            ),
+            out_amount,
+        )?;
+
+        in_vault.total_amount = in_vault
+            .total_amount
+            .checked_add(in_amount)
+            .ok_or(MockError::MathOverflow)?;
+        out_vault.total_amount = out_vault
+            .total_amount
+            .checked_sub(out_amount)
+            .ok_or(MockError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Vault::LEN,
+        seeds = [b"vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"token_vault", vault.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = vault,
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", vault.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = vault,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(init, payer = payer, space = 8 + Pool::LEN)]
+    pub pool: Account<'info, Pool>,
+    pub a_vault: Account<'info, Vault>,
+    pub b_vault: Account<'info, Vault>,
+    #[account(address = a_vault.lp_mint)]
+    pub a_vault_lp_mint: Account<'info, Mint>,
+    #[account(address = b_vault.lp_mint)]
+    pub b_vault_lp_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [a_vault.key().as_ref(), pool.key().as_ref()],
+        bump,
+        token::mint = a_vault_lp_mint,
+        token::authority = a_vault,
+    )]
+    pub a_vault_lp: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b_vault.key().as_ref(), pool.key().as_ref()],
+        bump,
+        token::mint = b_vault_lp_mint,
+        token::authority = b_vault,
+    )]
+    pub b_vault_lp: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut, address = vault.token_vault)]
+    pub token_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = vault.lp_mint)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(mut, token::mint = lp_mint)]
+    pub pool_lp: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub depositor_token: Account<'info, TokenAccount>,
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Meteora's swap accounts, in Meteora's order
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        has_one = a_vault,
+        has_one = b_vault,
+        has_one = a_vault_lp,
+        has_one = b_vault_lp
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub user_source_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_destination_token: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub a_vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub b_vault: Account<'info, Vault>,
+    #[account(mut, address = a_vault.token_vault)]
+    pub a_token_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = b_vault.token_vault)]
+    pub b_token_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = a_vault.lp_mint)]
+    pub a_vault_lp_mint: Account<'info, Mint>,
+    #[account(mut, address = b_vault.lp_mint)]
+    pub b_vault_lp_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub a_vault_lp: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub b_vault_lp: Account<'info, TokenAccount>,
+    /// CHECK: The mock charges no protocol fee
+    #[account(mut)]
+    pub protocol_token_fee: UncheckedAccount<'info>,
+    pub user: Signer<'info>,
+    /// CHECK: The mock moves vault funds itself instead of calling the vault program
+    pub vault_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+pub struct Pool {
+    pub a_vault: Pubkey,
+    pub b_vault: Pubkey,
+    pub a_vault_lp: Pubkey,
+    pub b_vault_lp: Pubkey,
+    pub trade_fee_bps: u16,
+}
+
+impl Pool {
+    pub const LEN: usize = 32 * 4 + 2;
+}
+
+/// Prefix of Meteora's vault account; `total_amount` sits at the same offset
+#[account]
+pub struct Vault {
+    pub enabled: u8,
+    pub bumps: [u8; 2],             // Vault and token vault bumps
+    pub total_amount: u64,
+    pub token_vault: Pubkey,
+    pub fee_vault: Pubkey,
+    pub token_mint: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+impl Vault {
+    pub const LEN: usize = 1 + 2 + 8 + 32 * 4;
+}
+
+#[error_code]
+pub enum MockError {
+    #[msg("Trade fee must be below 100%")]
+    InvalidFee,
+    #[msg("Destination account is not for the pool's other mint")]
+    InvalidMint,
+    #[msg("Swap output below the minimum")]
+    ExceededSlippage,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,86 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getMint } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  eventsIn,
+  collateralConfigFor,
+  creationSignature,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  claimReward,
+  resolveMarket,
+  Platform,
+} from "./fixtures";
+
+// The BelieveApp backend replays BurnEvents by sequence number, so every burn
+// has to be reported once, with the amount that actually left the supply and
+// the next number of its mint's burn sequence.
+describe("BelieveApp burn events", () => {
+  let platform: Platform;
+
+  const supply = async () =>
+    Number((await getMint(provider.connection, platform.mint)).supply);
+
+  const burnSequence = async () =>
+    (await program.account.collateralConfig.fetch(collateralConfigFor(platform.mint))).burnSequence.toNumber();
+
+  const burnEvents = async (signature: string) =>
+    (await eventsIn(signature))
+      .filter((event) => event.name.toLowerCase() === "burnevent")
+      .map((event) => event.data as any);
+
+  // Runs `action` and checks it reported exactly one burn, numbered after the
+  // last one and matching the supply the mint lost
+  const singleBurn = async (action: () => Promise<string>) => {
+    const [sequenceBefore, supplyBefore] = [await burnSequence(), await supply()];
+    const events = await burnEvents(await action());
+
+    assert.lengthOf(events, 1);
+    const [event] = events;
+    assert.ok(event.mint.equals(platform.mint));
+    assert.equal(event.sequence.toNumber(), sequenceBefore + 1);
+    assert.equal(await burnSequence(), sequenceBefore + 1);
+    assert.equal(event.burnAmount.toNumber(), supplyBefore - (await supply()));
+    return event;
+  };
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("numbers creation, bet and claim burns consecutively", async () => {
+    let market: anchor.web3.PublicKey;
+    const created = await singleBurn(async () => {
+      market = await createMarket(platform, "seq-1");
+      return creationSignature(market);
+    });
+    assert.ok(created.burnType.marketCreation);
+    assert.ok(created.user.equals(authority));
+    assert.ok(created.market.equals(market));
+    assert.equal(created.amount.toNumber(), 10_000_000);
+    assert.isNull(created.predictionOption);
+
+    const bet = await singleBurn(() => placePrediction(platform, market, 1, 4_000_000));
+    assert.ok(bet.burnType.predictionBet);
+    assert.equal(bet.amount.toNumber(), 4_000_000);
+    assert.equal(bet.burnAmount.toNumber(), 100_000);
+    assert.equal(bet.predictionOption, "No");
+
+    await resolveMarket(market, 1);
+    const claim = await singleBurn(() => claimReward(platform, market, 1));
+    assert.ok(claim.burnType.rewardClaim);
+    assert.ok(claim.market.equals(market));
+    assert.isNull(claim.predictionOption);
+    assert.equal(claim.sequence.toNumber(), bet.sequence.toNumber() + 1);
+  });
+
+  it("names the option without its padding", async () => {
+    const market = await createMarket(platform, "seq-2");
+    const bet = await singleBurn(() => placePrediction(platform, market, 0));
+    assert.equal(bet.predictionOption, "Yes");
+  });
+});
@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  escrowFor,
+  predictionFor,
+  referralFor,
+  burnReceiptFor,
+  nextBurnNonce,
+  setupPlatform,
+  createMarket,
+  Platform,
+} from "./fixtures";
+
+// Burn receipts are addressed by market, burner and nonce, so concurrent
+// burns never compete for the same account, and the relayer closes each one
+// after acknowledging it to hand the rent back to the burner.
+describe("burn receipts", () => {
+  let platform: Platform;
+  const relayer = anchor.web3.Keypair.generate();
+
+  const bet = (market: anchor.web3.PublicKey, nonce: BN) =>
+    program.methods
+      .placePrediction(0, new BN(5_000_000), nonce)
+      .accountsPartial({
+        market,
+        platformState,
+        user: authority,
+        userTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        treasuryVault: platform.treasuryVault,
+        feeLedger: platform.feeLedger,
+        prediction: predictionFor(market, 0),
+        pmpTokenAccount: null,
+        referral: referralFor(authority, platform.mint),
+        referrerRewards: null,
+        burnReceipt: burnReceiptFor(market, authority, nonce),
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  const acknowledge = (burnReceipt: anchor.web3.PublicKey) =>
+    program.methods
+      .acknowledgeBurn(Array.from(Buffer.alloc(64, "p")), 8)
+      .accountsPartial({ platformState, burnReceipt, relayer: relayer.publicKey })
+      .signers([relayer])
+      .rpc();
+
+  const close = (burnReceipt: anchor.web3.PublicKey, signer = relayer) =>
+    program.methods
+      .closeBurnReceipt()
+      .accountsPartial({ platformState, burnReceipt, payer: authority, relayer: signer.publicKey })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+    await program.methods
+      .setBurnRelayer(relayer.publicKey)
+      .accountsPartial({ platformState, authority })
+      .rpc();
+  });
+
+  it("keeps one receipt per nonce with its own sequence", async () => {
+    const market = await createMarket(platform, "receipt-1");
+    const first = nextBurnNonce();
+    const second = nextBurnNonce();
+    await Promise.all([bet(market, first), bet(market, second)]);
+
+    const a = await program.account.burnReceipt.fetch(burnReceiptFor(market, authority, first));
+    const b = await program.account.burnReceipt.fetch(burnReceiptFor(market, authority, second));
+    assert.notEqual(a.sequence.toString(), b.sequence.toString());
+    assert.equal(a.nonce.toString(), first.toString());
+
+    await expectError(bet(market, first), "already in use");
+  });
+
+  it("closes only acknowledged receipts, for the relayer", async () => {
+    const market = await createMarket(platform, "receipt-2");
+    const nonce = nextBurnNonce();
+    await bet(market, nonce);
+    const receipt = burnReceiptFor(market, authority, nonce);
+
+    await expectError(close(receipt), "BurnNotAcknowledged");
+    await acknowledge(receipt);
+    await expectError(close(receipt, anchor.web3.Keypair.generate()), "Unauthorized");
+
+    // The rent goes back to the burner; the provider wallet also pays the fee
+    const rent = (await provider.connection.getAccountInfo(receipt)).lamports;
+    const before = await provider.connection.getBalance(authority);
+    await close(receipt);
+    assert.isNull(await provider.connection.getAccountInfo(receipt));
+    const refunded = (await provider.connection.getBalance(authority)) - before;
+    assert.isAbove(refunded, rent - 20_000);
+  });
+});
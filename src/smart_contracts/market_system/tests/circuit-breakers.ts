@@ -0,0 +1,194 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  escrowFor,
+  predictionFor,
+  collateralConfigFor,
+  sponsorshipFor,
+  setPauseFlags,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  claimReward,
+  resolveMarket,
+  cancelMarket,
+  Platform,
+} from "./fixtures";
+
+const PAUSE_CREATION = 1 << 0;
+const PAUSE_BETTING = 1 << 1;
+const PAUSE_RESOLUTION = 1 << 2;
+const PAUSE_PAYOUTS = 1 << 3;
+const PAUSE_REFUNDS = 1 << 4;
+const PAUSE_ALL = (1 << 5) - 1;
+
+// Each pause flag halts its own set of handlers and leaves the rest running,
+// so an incident in one flow doesn't strand funds in another. Sponsoring adds
+// to the pools like a bet and taking a subsidy back moves funds out like a
+// refund, so they stop with betting and refunds.
+describe("circuit breakers", () => {
+  let platform: Platform;
+
+  const refund = (market: anchor.web3.PublicKey, option: number) =>
+    program.methods
+      .emergencyWithdraw(option)
+      .accountsPartial({
+        market,
+        platformState,
+        prediction: predictionFor(market, option),
+        user: authority,
+        userTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  const sponsor = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .sponsorMarket(new BN(2_000_000), [new BN(1_000_000), new BN(0), new BN(0), new BN(0)])
+      .accountsPartial({
+        market,
+        platformState,
+        sponsor: authority,
+        sponsorTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        sponsorship: sponsorshipFor(market, authority),
+        collateralConfig: collateralConfigFor(platform.mint),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  const withdraw = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .withdrawSponsorship()
+      .accountsPartial({
+        market,
+        platformState,
+        sponsor: authority,
+        sponsorship: sponsorshipFor(market, authority),
+        sponsorTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  afterEach(async () => {
+    await setPauseFlags(0);
+  });
+
+  it("only accepts known flags from the platform authority", async () => {
+    await expectError(setPauseFlags(1 << 5), "InvalidPauseFlags");
+
+    const stranger = anchor.web3.Keypair.generate();
+    await expectError(
+      program.methods
+        .setPauseFlags(PAUSE_ALL)
+        .accountsPartial({ platformState, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await setPauseFlags(PAUSE_ALL);
+    const state = await program.account.platformState.fetch(platformState);
+    assert.equal(state.pauseFlags, PAUSE_ALL);
+  });
+
+  it("halts market creation while betting carries on", async () => {
+    const market = await createMarket(platform, "breaker-creation-1");
+
+    await setPauseFlags(PAUSE_CREATION);
+    await expectError(createMarket(platform, "breaker-creation-2"), "CreationPaused");
+    await placePrediction(platform, market, 0);
+  });
+
+  it("halts betting while markets still resolve and pay out", async () => {
+    const market = await createMarket(platform, "breaker-betting");
+    await placePrediction(platform, market, 0);
+
+    await setPauseFlags(PAUSE_BETTING);
+    await expectError(placePrediction(platform, market, 1), "BettingPaused");
+
+    await resolveMarket(market, 0);
+    await claimReward(platform, market, 0);
+  });
+
+  it("halts resolution and payouts independently", async () => {
+    const resolved = await createMarket(platform, "breaker-resolution-1");
+    const cancelled = await createMarket(platform, "breaker-resolution-2");
+    await placePrediction(platform, resolved, 1);
+
+    await setPauseFlags(PAUSE_RESOLUTION);
+    await expectError(resolveMarket(resolved, 1), "ResolutionPaused");
+    await expectError(cancelMarket(cancelled), "ResolutionPaused");
+
+    await setPauseFlags(PAUSE_PAYOUTS);
+    await resolveMarket(resolved, 1);
+    await cancelMarket(cancelled);
+    await expectError(claimReward(platform, resolved, 1), "PayoutsPaused");
+
+    await setPauseFlags(0);
+    await claimReward(platform, resolved, 1);
+    const prediction = await program.account.prediction.fetch(predictionFor(resolved, 1));
+    assert.isTrue(prediction.claimed);
+  });
+
+  it("keeps refunds open when everything else is halted", async () => {
+    const market = await createMarket(platform, "breaker-refunds");
+    await placePrediction(platform, market, 0);
+    await cancelMarket(market);
+
+    await setPauseFlags(PAUSE_REFUNDS);
+    await expectError(refund(market, 0), "RefundsPaused");
+
+    await setPauseFlags(PAUSE_ALL & ~PAUSE_REFUNDS);
+    const before = (await getAccount(provider.connection, platform.userToken)).amount;
+    await refund(market, 0);
+    const after = (await getAccount(provider.connection, platform.userToken)).amount;
+    assert.isTrue(after > before);
+  });
+
+  it("blocks sponsoring while betting is paused, not withdrawing", async () => {
+    const market = await createMarket(platform, "breaker-sponsor-1");
+
+    await setPauseFlags(PAUSE_BETTING);
+    await expectError(sponsor(market), "BettingPaused");
+
+    await setPauseFlags(0);
+    await sponsor(market);
+    await cancelMarket(market);
+
+    await setPauseFlags(PAUSE_BETTING);
+    await withdraw(market);
+    const sponsorship = await program.account.sponsorship.fetch(sponsorshipFor(market, authority));
+    assert.isTrue(sponsorship.refunded);
+  });
+
+  it("blocks withdrawing while refunds are paused, not sponsoring", async () => {
+    const market = await createMarket(platform, "breaker-sponsor-2");
+
+    await setPauseFlags(PAUSE_REFUNDS);
+    await sponsor(market);
+    await cancelMarket(market);
+    await expectError(withdraw(market), "RefundsPaused");
+
+    await setPauseFlags(0);
+    await withdraw(market);
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.subsidyRefunded.toString(), account.subsidyTotal.toString());
+  });
+});
@@ -0,0 +1,43 @@
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  expectError,
+  setupPlatform,
+  addCollateral,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Collateral limits are whole tokens, so a one-token floor means 10^decimals
+// base units whatever the mint.
+describe("collateral limits", () => {
+  let sixDecimals: Platform;
+  let nineDecimals: Platform;
+
+  before(async () => {
+    sixDecimals = await setupPlatform();
+    nineDecimals = await addCollateral(9);
+  });
+
+  it("scales the minimum bet by the mint's decimals", async () => {
+    await expectError(
+      createMarket(nineDecimals, "limits-low", { minBet: new BN(999_999_999) }),
+      "BetTooSmall"
+    );
+    const market = await createMarket(nineDecimals, "limits-nine", { minBet: new BN(1_000_000_000) });
+    await placePrediction(nineDecimals, market, 0, 1_000_000_000);
+
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.minBetAmount.toString(), "1000000000");
+  });
+
+  it("uses the same whole-token floor for a six-decimal mint", async () => {
+    await expectError(
+      createMarket(sixDecimals, "limits-six-low", { minBet: new BN(999_999) }),
+      "BetTooSmall"
+    );
+    await createMarket(sixDecimals, "limits-six", { minBet: new BN(1_000_000) });
+  });
+});
@@ -0,0 +1,94 @@
+import { BN } from "@coral-xyz/anchor";
+import { createMint, getMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  expectError,
+  eventsIn,
+  collateralConfigFor,
+  setCollateralConfig,
+  setupPlatform,
+  addCollateral,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Markets can only be opened on whitelisted, enabled collateral mints, and
+// each mint carries its own minimum bet, burn switch and platform fee cap.
+describe("collateral whitelist", () => {
+  let platform: Platform;
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("rejects markets on mints that were never whitelisted", async () => {
+    const unlisted = await createMint(provider.connection, payer, authority, null, 6);
+    const userToken = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, unlisted, authority)
+    ).address;
+    await mintTo(provider.connection, payer, unlisted, userToken, payer, 100_000_000);
+
+    await expectError(
+      createMarket({ ...platform, mint: unlisted, userToken }, "collateral-1"),
+      "AccountNotInitialized"
+    );
+  });
+
+  it("floors market minimums and bets at the mint's minimum bet", async () => {
+    const collateral = await addCollateral(6);
+    await setCollateralConfig(collateral.mint, { minBetTokens: 2 });
+
+    // The fixture market asks for a one-token minimum
+    await expectError(createMarket(collateral, "collateral-2"), "BetTooSmall");
+    const market = await createMarket(collateral, "collateral-3", { minBet: new BN(2_000_000) });
+    await placePrediction(collateral, market, 0, 2_000_000);
+  });
+
+  it("skips burns and caps the platform fee by the mint's settings", async () => {
+    const collateral = await addCollateral(6);
+    await setCollateralConfig(collateral.mint, { burnEnabled: false, platformFeeRate: 50 });
+    const supply = async () => (await getMint(provider.connection, collateral.mint)).supply;
+    const platformBefore = await program.account.platformState.fetch(platformState);
+    const supplyBefore = await supply();
+
+    const market = await createMarket(collateral, "collateral-4");
+    const [placed] = (await eventsIn(await placePrediction(collateral, market, 0, 10_000_000)))
+      .filter((event) => event.name.toLowerCase() === "predictionplaced")
+      .map((event) => event.data as any);
+
+    assert.equal(placed.burnAmount.toNumber(), 0);
+    assert.equal(placed.platformFeeRate.toNumber(), 50);
+    assert.equal(placed.platformFee.toNumber(), 50_000);
+    assert.equal(await supply(), supplyBefore);
+
+    // Activity is counted on the mint, and only the platform token's totals
+    // are kept on the platform
+    const config = await program.account.collateralConfig.fetch(collateralConfigFor(collateral.mint));
+    assert.equal(config.totalVolume.toNumber(), 10_000_000);
+    assert.equal(config.totalBurned.toNumber(), 0);
+    const platformAfter = await program.account.platformState.fetch(platformState);
+    assert.equal(platformAfter.totalVolume.toString(), platformBefore.totalVolume.toString());
+  });
+
+  it("caps bets and pools in whole tokens of the mint", async () => {
+    const collateral = await addCollateral(6);
+    await expectError(
+      setCollateralConfig(collateral.mint, { maxBetTokens: 13, maxPoolTokens: 12 }),
+      "InvalidAmount"
+    );
+    await setCollateralConfig(collateral.mint, { maxBetTokens: 5, maxPoolTokens: 12 });
+
+    const market = await createMarket(collateral, "collateral-5", { stake: new BN(5_000_000) });
+    await expectError(placePrediction(collateral, market, 0, 5_000_001), "BetTooLarge");
+    await placePrediction(collateral, market, 0, 5_000_000);
+    await placePrediction(collateral, market, 1, 5_000_000);
+    // Net of fees and burns the pool holds a little under ten tokens
+    await expectError(placePrediction(collateral, market, 0, 5_000_000), "PoolOverflow");
+  });
+});
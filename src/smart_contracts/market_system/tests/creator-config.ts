@@ -0,0 +1,65 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  authority,
+  accessControl,
+  expectError,
+  creatorConfigFor,
+  setupPlatform,
+  fundedUser,
+  createMarket,
+  resolveMarket,
+  cancelMarket,
+  Bettor,
+  Platform,
+} from "./fixtures";
+
+// Whitelisted creators other than the admin are bound by their creator
+// config: a fee cap, a whole-token stake floor, allowed categories and a quota
+// of active markets that frees up as their markets resolve or are cancelled.
+describe("creator config", () => {
+  let platform: Platform;
+  let creator: Bettor;
+
+  const configure = (maxActiveMarkets: number, minStakeTokens: number) =>
+    program.methods
+      .setCreatorConfig(creator.user.publicKey, new BN(200), maxActiveMarkets, new BN(minStakeTokens), [])
+      .accountsPartial({ accessControl, creatorConfig: creatorConfigFor(creator.user.publicKey), admin: authority })
+      .rpc();
+
+  const activeMarkets = async () =>
+    (await program.account.creatorConfig.fetch(creatorConfigFor(creator.user.publicKey))).activeMarkets;
+
+  const create = (id: string, stake = 10_000_000) =>
+    createMarket(platform, id, { stake: new BN(stake), creator });
+
+  before(async () => {
+    platform = await setupPlatform();
+    creator = await fundedUser(platform, 100_000_000);
+    await program.methods
+      .addMarketCreator(creator.user.publicKey)
+      .accountsPartial({ accessControl, admin: authority })
+      .rpc();
+    await configure(2, 10);
+  });
+
+  it("holds the stake floor in whole tokens", async () => {
+    const config = await program.account.creatorConfig.fetch(creatorConfigFor(creator.user.publicKey));
+    assert.equal(config.minStakeTokens.toNumber(), 10);
+    await expectError(create("creator-1", 9_999_999), "CreatorStakeTooLow");
+  });
+
+  it("frees a quota slot when a market resolves or is cancelled", async () => {
+    const resolved = await create("creator-2");
+    const cancelled = await create("creator-3");
+    assert.equal(await activeMarkets(), 2);
+    await expectError(create("creator-4"), "ActiveMarketLimitReached");
+
+    await resolveMarket(resolved, 0);
+    assert.equal(await activeMarkets(), 1);
+    await cancelMarket(cancelled);
+    assert.equal(await activeMarkets(), 0);
+  });
+});
@@ -0,0 +1,185 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  expectError,
+  escrowFor,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  claimReward,
+  resolveMarket,
+  cancelMarket,
+  Platform,
+} from "./fixtures";
+
+// The reconciliation view compares what a market's escrow owes with what it
+// holds; the platform authority can move out the difference and nothing more.
+// A whitelisted creator's stake sits in the escrow next to the pool and is
+// owed until it is released.
+describe("escrow reconciliation", () => {
+  let platform: Platform;
+
+  const reconcile = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .escrowReconciliation()
+      .accountsPartial({ market, marketEscrow: escrowFor(market) })
+      .view();
+
+  const pda = (...seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const recover = (
+    market: anchor.web3.PublicKey,
+    amount: number,
+    {
+      source = escrowFor(market),
+      mint = platform.mint,
+      destination = pda(Buffer.from("treasury_vault"), mint.toBuffer()),
+      signer,
+    }: {
+      source?: anchor.web3.PublicKey;
+      mint?: anchor.web3.PublicKey;
+      destination?: anchor.web3.PublicKey;
+      signer?: anchor.web3.Keypair;
+    } = {}
+  ) =>
+    program.methods
+      .recoverEscrowSurplus(new BN(amount))
+      .accountsPartial({
+        market,
+        platformState,
+        authority: signer?.publicKey ?? authority,
+        marketEscrow: escrowFor(market),
+        sourceTokenAccount: source,
+        destinationTokenAccount: destination,
+        feeLedger: pda(Buffer.from("fee_ledger"), mint.toBuffer()),
+        tokenMint: mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("owes the pool and the creator's stake of a live market", async () => {
+    const market = await createMarket(platform, "reconcile-1");
+    const stakeOnly = await reconcile(market);
+    await placePrediction(platform, market, 0);
+
+    const account = await program.account.market.fetch(market);
+    assert.isTrue(account.creatorStake.gtn(0));
+    assert.equal(stakeOnly.owed.toString(), account.creatorStake.toString());
+    assert.equal(stakeOnly.surplus.toNumber(), 0);
+
+    const balance = await reconcile(market);
+    assert.equal(balance.owed.toString(), account.totalPool.add(account.creatorStake).toString());
+    const escrow = await getAccount(provider.connection, escrowFor(market));
+    assert.equal(balance.held.toString(), escrow.amount.toString());
+    assert.equal(balance.surplus.toNumber(), 0);
+  });
+
+  it("recovers a mis-sent deposit into the treasury but never owed funds", async () => {
+    const market = await createMarket(platform, "reconcile-2");
+    await placePrediction(platform, market, 1);
+    const before = await reconcile(market);
+
+    await transfer(provider.connection, payer, platform.userToken, escrowFor(market), payer, 3_000_000);
+    const balance = await reconcile(market);
+    assert.equal(balance.surplus.sub(before.surplus).toNumber(), 3_000_000);
+    await expectError(recover(market, balance.surplus.toNumber() + 1), "InsufficientSurplus");
+
+    const stranger = anchor.web3.Keypair.generate();
+    await expectError(recover(market, 1, { signer: stranger }), "Unauthorized");
+
+    await expectError(recover(market, 1, { destination: platform.userToken }), "ConstraintSeeds");
+
+    const ledgerBefore = await program.account.feeLedger.fetch(platform.feeLedger);
+    const vaultBefore = await getAccount(provider.connection, platform.treasuryVault);
+    await recover(market, 3_000_000);
+    const ledgerAfter = await program.account.feeLedger.fetch(platform.feeLedger);
+    const vaultAfter = await getAccount(provider.connection, platform.treasuryVault);
+    assert.equal(Number(vaultAfter.amount - vaultBefore.amount), 3_000_000);
+    assert.equal(ledgerAfter.treasuryAccrued.sub(ledgerBefore.treasuryAccrued).toNumber(), 3_000_000);
+
+    const after = await reconcile(market);
+    assert.equal(after.surplus.toString(), before.surplus.toString());
+    assert.equal(after.owed.toString(), before.owed.toString());
+  });
+
+  it("drains other token accounts owned by the escrow into their mint's treasury", async () => {
+    const market = await createMarket(platform, "reconcile-3");
+    const strayMint = await createMint(provider.connection, payer, authority, null, 9);
+    const stray = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, strayMint, escrowFor(market), true)
+    ).address;
+    await mintTo(provider.connection, payer, strayMint, stray, payer, 42);
+
+    const strayVault = pda(Buffer.from("treasury_vault"), strayMint.toBuffer());
+    await program.methods
+      .initializeTreasuryVault()
+      .accountsPartial({
+        platformState,
+        authority,
+        treasuryVault: strayVault,
+        feeLedger: pda(Buffer.from("fee_ledger"), strayMint.toBuffer()),
+        tokenMint: strayMint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+    await recover(market, 42, { source: stray, mint: strayMint });
+    assert.equal(Number((await getAccount(provider.connection, strayVault)).amount), 42);
+    assert.equal(Number((await getAccount(provider.connection, stray)).amount), 0);
+  });
+
+  it("stops owing what has been claimed", async () => {
+    const market = await createMarket(platform, "reconcile-4");
+    await placePrediction(platform, market, 0);
+    await resolveMarket(market, 0);
+    await claimReward(platform, market, 0);
+
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.totalClaimed.toString(), account.totalPool.toString());
+    assert.equal((await reconcile(market)).owed.toString(), account.creatorStake.toString());
+  });
+
+  it("stops owing the stake once it is returned to the creator", async () => {
+    const market = await createMarket(platform, "reconcile-5");
+    await cancelMarket(market);
+    await expectError(recover(market, 1), "InsufficientSurplus");
+
+    await program.methods
+      .releaseCreatorBond()
+      .accountsPartial({
+        market,
+        platformState,
+        creator: authority,
+        creatorTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.creatorStake.toNumber(), 0);
+    const balance = await reconcile(market);
+    assert.equal(balance.owed.toNumber(), 0);
+    assert.equal(balance.held.toNumber(), 0);
+  });
+});
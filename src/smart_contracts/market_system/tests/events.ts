@@ -0,0 +1,123 @@
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  authority,
+  platformState,
+  eventsIn,
+  creationSignature,
+  escrowFor,
+  predictionFor,
+  setPauseFlags,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  claimReward,
+  resolveMarket,
+  cancelMarket,
+  Platform,
+} from "./fixtures";
+
+// State transitions are indexed from typed events rather than msg! logs, so
+// each event has to carry the same numbers the accounts end up with.
+describe("typed events", () => {
+  let platform: Platform;
+
+  // The single event of `name` in a transaction; event names are matched
+  // case-insensitively since the client camel-cases them
+  const only = async (signature: string, name: string) => {
+    const events = (await eventsIn(signature)).filter(
+      (event) => event.name.toLowerCase() === name.toLowerCase()
+    );
+    assert.lengthOf(events, 1, `${name} events`);
+    return events[0].data as any;
+  };
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("reports market creation with the stake split", async () => {
+    const market = await createMarket(platform, "events-1");
+    const event = await only(await creationSignature(market), "MarketCreated");
+
+    assert.ok(event.market.equals(market));
+    assert.ok(event.creator.equals(authority));
+    assert.ok(event.status.active);
+    assert.equal(event.optionCount, 2);
+    assert.equal(event.stakeAmount.toNumber(), 10_000_000);
+    assert.equal(event.burnAmount.toNumber(), 50_000);
+    assert.equal(event.creatorBond.toNumber(), 0);
+  });
+
+  it("reports each bet's split and the pools it lands in", async () => {
+    const market = await createMarket(platform, "events-2");
+    await placePrediction(platform, market, 1, 2_000_000);
+    const event = await only(await placePrediction(platform, market, 1, 4_000_000), "PredictionPlaced");
+
+    // 2.5% burned and 1% to the treasury vault
+    assert.equal(event.optionIndex, 1);
+    assert.equal(event.amount.toNumber(), 4_000_000);
+    assert.equal(event.burnAmount.toNumber(), 100_000);
+    assert.equal(event.platformFee.toNumber(), 40_000);
+    assert.equal(event.netAmount.toNumber(), 3_860_000);
+
+    const account = await program.account.market.fetch(market);
+    const prediction = await program.account.prediction.fetch(predictionFor(market, 1));
+    assert.equal(event.positionAmount.toString(), prediction.amount.toString());
+    assert.equal(event.optionPool.toString(), account.option2Pool.toString());
+    assert.equal(event.totalPool.toString(), account.totalPool.toString());
+  });
+
+  it("reports resolution and the claim that pays it out", async () => {
+    const market = await createMarket(platform, "events-3");
+    await placePrediction(platform, market, 0);
+    await placePrediction(platform, market, 1);
+
+    const resolved = await only(await resolveMarket(market, 0), "MarketResolved");
+    const account = await program.account.market.fetch(market);
+    assert.equal(resolved.winningOption, 0);
+    assert.equal(resolved.winningPool.toString(), account.option1Pool.toString());
+    assert.equal(resolved.totalPool.toString(), account.totalPool.toString());
+
+    const claimed = await only(await claimReward(platform, market, 0), "RewardClaimed");
+    assert.equal(claimed.rewardAmount.toString(), account.totalPool.toString());
+    assert.equal(
+      claimed.netReward.add(claimed.creatorFee).add(claimed.burnAmount).toString(),
+      claimed.rewardAmount.toString()
+    );
+  });
+
+  it("reports cancellation and each refund", async () => {
+    const market = await createMarket(platform, "events-4");
+    await placePrediction(platform, market, 0);
+    const { totalPool } = await program.account.market.fetch(market);
+
+    const cancelled = await only(await cancelMarket(market), "MarketCancelled");
+    assert.equal(cancelled.totalPool.toString(), totalPool.toString());
+
+    const signature = await program.methods
+      .emergencyWithdraw(0)
+      .accountsPartial({
+        market,
+        platformState,
+        prediction: predictionFor(market, 0),
+        user: authority,
+        userTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+    const refunded = await only(signature, "PredictionRefunded");
+    assert.equal(refunded.amount.toString(), totalPool.toString());
+    assert.equal(refunded.totalRefunded.toString(), totalPool.toString());
+  });
+
+  it("reports the previous and new pause flags", async () => {
+    const event = await only(await setPauseFlags(1 << 2), "PauseFlagsUpdated");
+    await setPauseFlags(0);
+    assert.equal(event.previousFlags, 0);
+    assert.equal(event.pauseFlags, 1 << 2);
+  });
+});
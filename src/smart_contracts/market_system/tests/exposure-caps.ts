@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  authority,
+  expectError,
+  predictionFor,
+  fundedUser,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Markets can cap how much one user holds on an option, in tokens and as a
+// share of the option pool. Caps apply to the position after the bet lands.
+describe("exposure caps", () => {
+  let platform: Platform;
+
+  const setMarketLimits = (
+    market: anchor.web3.PublicKey,
+    maxUserStake: number,
+    maxOptionShareBps: number,
+    signer?: anchor.web3.Keypair
+  ) =>
+    program.methods
+      .setMarketLimits(new BN(maxUserStake), maxOptionShareBps)
+      .accountsPartial({ market, authority: signer?.publicKey ?? authority })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("only lets the market authority or creator set valid limits", async () => {
+    const market = await createMarket(platform, "exposure-1");
+    const stranger = await fundedUser(platform);
+
+    await expectError(setMarketLimits(market, 0, 2_500, stranger.user), "Unauthorized");
+    await expectError(setMarketLimits(market, 0, 10_001), "InvalidMarketLimits");
+    // Below the market's one-token minimum bet
+    await expectError(setMarketLimits(market, 999_999, 0), "InvalidMarketLimits");
+
+    await setMarketLimits(market, 6_000_000, 2_500);
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.maxUserStake.toNumber(), 6_000_000);
+    assert.equal(account.maxOptionShareBps, 2_500);
+  });
+
+  it("caps the accumulated position, not each bet", async () => {
+    const market = await createMarket(platform, "exposure-2");
+    await setMarketLimits(market, 6_000_000, 0);
+
+    // 4.825 tokens land after burn and fee, so a second two-token bet goes over
+    await placePrediction(platform, market, 0, 5_000_000);
+    await expectError(placePrediction(platform, market, 0, 2_000_000), "PositionLimitExceeded");
+    await placePrediction(platform, market, 0, 1_000_000);
+  });
+
+  it("exempts only the bet that seeds an empty option from the share cap", async () => {
+    const market = await createMarket(platform, "exposure-3");
+    const bettor = await fundedUser(platform);
+    await setMarketLimits(market, 0, 2_500);
+
+    await placePrediction(platform, market, 1, 1_000_000);
+    const prediction = await program.account.prediction.fetch(predictionFor(market, 1));
+    const state = await program.account.market.fetch(market);
+    assert.equal(prediction.amount.toString(), state.option2Pool.toString());
+
+    // A minimum-sized bet into the thin pool is capped like any other
+    await expectError(placePrediction(platform, market, 1, 1_000_000, { bettor }), "OptionShareExceeded");
+    await placePrediction(platform, market, 0, 20_000_000);
+    await placePrediction(platform, market, 0, 1_000_000, { bettor });
+  });
+
+  it("caps a sole bettor's second bet", async () => {
+    const market = await createMarket(platform, "exposure-4");
+    await setMarketLimits(market, 0, 2_500);
+
+    await placePrediction(platform, market, 0, 1_000_000);
+    await expectError(placePrediction(platform, market, 0, 1_000_000), "OptionShareExceeded");
+  });
+});
@@ -0,0 +1,87 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  authority,
+  platformState,
+  expectError,
+  fundedUser,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Bet fees are split by the platform table into per-recipient balances in the
+// fee ledger instead of being sent to each recipient on every bet.
+describe("fee splits", () => {
+  let platform: Platform;
+
+  const split = (recipient: string, betBps: number, claimBps: number) => ({
+    recipient: { [recipient]: {} },
+    betBps,
+    claimBps,
+  });
+  const defaults = [split("treasury", 10000, 0), split("creator", 0, 10000)];
+
+  const setFeeSplits = (splits: object[], signer?: anchor.web3.Keypair) =>
+    program.methods
+      .setFeeSplits(splits)
+      .accountsPartial({ platformState, authority: signer?.publicKey ?? authority })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  const withdrawStakerFees = (amount: BN) =>
+    program.methods
+      .withdrawStakerFees(amount)
+      .accountsPartial({
+        platformState,
+        authority,
+        treasuryVault: platform.treasuryVault,
+        feeLedger: platform.feeLedger,
+        stakerRewardsAccount: platform.userToken,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  after(async () => {
+    await setFeeSplits(defaults);
+  });
+
+  it("accrues each recipient's share of a bet fee", async () => {
+    await setFeeSplits([split("treasury", 5000, 0), split("stakers", 5000, 0), split("creator", 0, 10000)]);
+    const market = await createMarket(platform, "fee-split-1");
+    const before = await program.account.feeLedger.fetch(platform.feeLedger);
+
+    await placePrediction(platform, market, 0, 10_000_000);
+
+    const after = await program.account.feeLedger.fetch(platform.feeLedger);
+    const stakers = after.stakersAccrued.sub(before.stakersAccrued);
+    const treasury = after.treasuryAccrued.sub(before.treasuryAccrued);
+    assert.isTrue(stakers.gtn(0));
+    assert.isTrue(treasury.sub(stakers).abs().lten(1));
+
+    await withdrawStakerFees(stakers);
+    const withdrawn = await program.account.feeLedger.fetch(platform.feeLedger);
+    assert.equal(withdrawn.stakersAccrued.toString(), before.stakersAccrued.toString());
+  });
+
+  it("rejects invalid tables, other signers and overdrawn shares", async () => {
+    await expectError(setFeeSplits([split("treasury", 9000, 10000)]), "InvalidFeeSplit");
+    await expectError(
+      setFeeSplits([split("treasury", 5000, 5000), split("treasury", 5000, 5000)]),
+      "InvalidFeeSplit"
+    );
+    const stranger = await fundedUser(platform);
+    await expectError(setFeeSplits(defaults, stranger.user), "Unauthorized");
+
+    const ledger = await program.account.feeLedger.fetch(platform.feeLedger);
+    await expectError(withdrawStakerFees(ledger.stakersAccrued.addn(1)), "InsufficientAccruedFees");
+  });
+});
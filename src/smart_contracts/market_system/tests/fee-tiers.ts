@@ -0,0 +1,80 @@
+import { BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  authority,
+  platformState,
+  expectError,
+  eventsIn,
+  userStatsFor,
+  fundedUser,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  Bettor,
+  Platform,
+} from "./fixtures";
+
+// Bet fees and burns drop to a tier's rates once the bettor's cumulative
+// volume, in whole tokens, reaches it. The platform is initialized with a 1%
+// fee and 2.5% burn.
+describe("volume fee tiers", () => {
+  let platform: Platform;
+  let bettor: Bettor;
+
+  const tier = (minVolume: number, platformFeeRate: number, betBurnRate: number) => ({
+    minVolume: new BN(minVolume),
+    platformFeeRate: new BN(platformFeeRate),
+    betBurnRate: new BN(betBurnRate),
+  });
+
+  const setFeeTiers = (tiers: object[]) =>
+    program.methods.setFeeTiers(tiers).accountsPartial({ platformState, authority }).rpc();
+
+  const placed = async (signature: string) =>
+    (await eventsIn(signature)).find((event) => event.name.toLowerCase() === "predictionplaced")
+      .data as any;
+
+  before(async () => {
+    platform = await setupPlatform();
+    bettor = await fundedUser(platform);
+  });
+
+  after(async () => {
+    await setFeeTiers([]);
+  });
+
+  it("charges the tier rates from the bet after the threshold is reached", async () => {
+    await setFeeTiers([tier(10, 50, 100), tier(50, 0, 50)]);
+    const market = await createMarket(platform, "tier-1");
+
+    // The bet that reaches the threshold still pays the base rates
+    const first = await placed(await placePrediction(platform, market, 0, 10_000_000, { bettor }));
+    assert.equal(first.platformFeeRate.toNumber(), 100);
+    assert.equal(first.betBurnRate.toNumber(), 250);
+    assert.equal(first.platformFee.toNumber(), 100_000);
+
+    const second = await placed(await placePrediction(platform, market, 0, 4_000_000, { bettor }));
+    assert.equal(second.platformFeeRate.toNumber(), 50);
+    assert.equal(second.betBurnRate.toNumber(), 100);
+    assert.equal(second.platformFee.toNumber(), 20_000);
+    assert.equal(second.burnAmount.toNumber(), 40_000);
+
+    const stats = await program.account.userStats.fetch(userStatsFor(bettor.user.publicKey, platform.mint));
+    assert.ok(stats.user.equals(bettor.user.publicKey));
+    assert.equal(stats.totalVolume.toNumber(), 14_000_000);
+    assert.equal(stats.betCount, 2);
+  });
+
+  it("only accepts rising thresholds that lower the rates", async () => {
+    // Above the base platform fee
+    await expectError(setFeeTiers([tier(1, 150, 100)]), "InvalidFeeTier");
+    // A later tier raising the previous tier's burn
+    await expectError(setFeeTiers([tier(1, 50, 50), tier(2, 50, 100)]), "InvalidFeeTier");
+    await expectError(setFeeTiers([tier(5, 50, 100), tier(5, 0, 0)]), "InvalidFeeTier");
+    await expectError(
+      setFeeTiers([1, 2, 3, 4, 5, 6].map((minVolume) => tier(minVolume, 0, 0))),
+      "InvalidFeeTier"
+    );
+  });
+});
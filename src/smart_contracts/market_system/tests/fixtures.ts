@@ -0,0 +1,404 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import {
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  ExtensionType,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Shared platform setup for the integration tests. The platform state is a
+// single PDA, so it is initialized once per validator and every test file
+// builds its markets on the same platform token, or on extra collateral
+// mints whitelisted next to it.
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const program = anchor.workspace.marketSystem as Program<any>;
+export const payer = (provider.wallet as anchor.Wallet).payer;
+export const authority = provider.wallet.publicKey;
+
+const pda = (...seeds: Buffer[]) =>
+  anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+export const platformState = pda(Buffer.from("platform_state"));
+// Owner of the account treasury withdrawals are sent to
+export const treasury = anchor.web3.Keypair.generate().publicKey;
+export const accessControl = pda(Buffer.from("access_control"));
+
+export const fixed = (text: string, len: number) => {
+  const bytes = Buffer.alloc(len);
+  bytes.write(text);
+  return Array.from(bytes);
+};
+
+export const expectError = async (promise: Promise<unknown>, code: string) => {
+  try {
+    await promise;
+    assert.fail(`expected ${code}`);
+  } catch (e) {
+    assert.include(e.toString(), code);
+  }
+};
+
+// Decoded Anchor events of a transaction, once it is confirmed
+export const eventsIn = async (signature: string) => {
+  await provider.connection.confirmTransaction(signature, "confirmed");
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  return Array.from(parser.parseLogs(tx.meta.logMessages));
+};
+
+// createMarket returns the market, so its creation is found by address
+export const creationSignature = async (market: anchor.web3.PublicKey) => {
+  for (let attempt = 0; attempt < 30; attempt++) {
+    const [first] = await provider.connection.getSignaturesForAddress(market, {}, "confirmed");
+    if (first) return first.signature;
+    await new Promise((resolve) => setTimeout(resolve, 500));
+  }
+  throw new Error("market creation not confirmed");
+};
+
+export const escrowFor = (market: anchor.web3.PublicKey) =>
+  pda(Buffer.from("market_escrow"), market.toBuffer());
+
+export const predictionFor = (
+  market: anchor.web3.PublicKey,
+  option: number,
+  user: anchor.web3.PublicKey = authority
+) => pda(Buffer.from("prediction"), market.toBuffer(), user.toBuffer(), Buffer.from([option]));
+
+// Receipts are keyed by market, burner and a caller-chosen nonce
+let burnNonce = 0;
+export const nextBurnNonce = () => new BN(++burnNonce);
+
+export const burnReceiptFor = (
+  market: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  nonce: BN
+) => pda(Buffer.from("burn_receipt"), market.toBuffer(), user.toBuffer(), nonce.toArrayLike(Buffer, "le", 8));
+
+export const collateralConfigFor = (mint: anchor.web3.PublicKey) =>
+  pda(Buffer.from("collateral"), mint.toBuffer());
+
+// The receipt for a burn on `mint`, or none when the mint has burns disabled.
+// Mints that aren't whitelisted get one so the program reports the mint.
+const burnReceiptOn = async (
+  mint: anchor.web3.PublicKey,
+  market: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  nonce: BN
+) => {
+  const config = await program.account.collateralConfig.fetchNullable(collateralConfigFor(mint));
+  return config && !config.burnEnabled ? null : burnReceiptFor(market, user, nonce);
+};
+
+export const userStatsFor = (user: anchor.web3.PublicKey, mint: anchor.web3.PublicKey) =>
+  pda(Buffer.from("user_stats"), user.toBuffer(), mint.toBuffer());
+
+export const referralFor = (user: anchor.web3.PublicKey, mint: anchor.web3.PublicKey) =>
+  pda(Buffer.from("referral"), user.toBuffer(), mint.toBuffer());
+
+export const referrerRewardsFor = (referrer: anchor.web3.PublicKey, mint: anchor.web3.PublicKey) =>
+  pda(Buffer.from("referrer_rewards"), referrer.toBuffer(), mint.toBuffer());
+
+export const creatorConfigFor = (creator: anchor.web3.PublicKey) =>
+  pda(Buffer.from("creator_config"), creator.toBuffer());
+
+// The creator config a market's quota slot is held against, if any
+const quotaConfigOf = async (market: anchor.web3.PublicKey) => {
+  const account = await program.account.market.fetch(market);
+  return account.tracksCreatorQuota ? creatorConfigFor(account.creator) : null;
+};
+
+export const sponsorshipFor = (market: anchor.web3.PublicKey, sponsor: anchor.web3.PublicKey) =>
+  pda(Buffer.from("sponsorship"), market.toBuffer(), sponsor.toBuffer());
+
+export const setPauseFlags = (flags: number) =>
+  program.methods.setPauseFlags(flags).accountsPartial({ platformState, authority }).rpc();
+
+export interface Platform {
+  mint: anchor.web3.PublicKey;
+  tokenProgram: anchor.web3.PublicKey;  // Token or Token-2022, passed to every transfer
+  userToken: anchor.web3.PublicKey;
+  treasuryVault: anchor.web3.PublicKey;
+  feeLedger: anchor.web3.PublicKey;
+  decimals: number;
+}
+
+let platform: Promise<Platform> | undefined;
+
+// A Token-2022 mint charging `transferFeeBps` on every transfer
+const createTransferFeeMint = async (decimals: number, transferFeeBps: number) => {
+  const mint = anchor.web3.Keypair.generate();
+  const space = getMintLen([ExtensionType.TransferFeeConfig]);
+  const tx = new anchor.web3.Transaction().add(
+    anchor.web3.SystemProgram.createAccount({
+      fromPubkey: authority,
+      newAccountPubkey: mint.publicKey,
+      space,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeTransferFeeConfigInstruction(
+      mint.publicKey, authority, authority, transferFeeBps, BigInt(2) ** BigInt(63), TOKEN_2022_PROGRAM_ID
+    ),
+    createInitializeMintInstruction(mint.publicKey, decimals, authority, null, TOKEN_2022_PROGRAM_ID)
+  );
+  await provider.sendAndConfirm(tx, [mint]);
+  return mint.publicKey;
+};
+
+const newMint = async (decimals: number, transferFeeBps?: number): Promise<Platform> => {
+  const tokenProgram = transferFeeBps === undefined ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const mint = transferFeeBps === undefined
+    ? await createMint(provider.connection, payer, authority, null, decimals)
+    : await createTransferFeeMint(decimals, transferFeeBps);
+  const userToken = (
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection, payer, mint, authority, false, undefined, undefined, tokenProgram
+    )
+  ).address;
+  await mintTo(
+    provider.connection, payer, mint, userToken, payer, BigInt(10) ** BigInt(decimals + 6), [], undefined, tokenProgram
+  );
+  return {
+    mint,
+    tokenProgram,
+    userToken,
+    treasuryVault: pda(Buffer.from("treasury_vault"), mint.toBuffer()),
+    feeLedger: pda(Buffer.from("fee_ledger"), mint.toBuffer()),
+    decimals,
+  };
+};
+
+export const setCollateralConfig = (
+  mint: anchor.web3.PublicKey,
+  {
+    enabled = true,
+    minBetTokens = 1,
+    burnEnabled = true,
+    platformFeeRate = 1000,
+    maxBetTokens = 1_000,
+    maxPoolTokens = 100_000,
+  }: {
+    enabled?: boolean;
+    minBetTokens?: number;
+    burnEnabled?: boolean;
+    platformFeeRate?: number;
+    maxBetTokens?: number;
+    maxPoolTokens?: number;
+  } = {}
+) =>
+  program.methods
+    .setCollateralConfig(
+      enabled,
+      new BN(minBetTokens),
+      burnEnabled,
+      new BN(platformFeeRate),
+      new BN(maxBetTokens),
+      new BN(maxPoolTokens)
+    )
+    .accountsPartial({ platformState, authority, tokenMint: mint })
+    .rpc();
+
+const enableCollateral = async ({ mint, tokenProgram, treasuryVault, feeLedger }: Platform) => {
+  await program.methods
+    .initializeTreasuryVault()
+    .accountsPartial({ platformState, authority, treasuryVault, feeLedger, tokenMint: mint, tokenProgram })
+    .rpc();
+  await setCollateralConfig(mint);
+};
+
+const initializePlatform = async (): Promise<Platform> => {
+  const collateral = await newMint(6);
+
+  await program.methods
+    .initialize(new BN(250), new BN(150), new BN(100))
+    .accountsPartial({
+      platformState,
+      authority,
+      tokenMint: collateral.mint,
+      treasury,
+      tokenProgram: collateral.tokenProgram,
+    })
+    .rpc();
+  await enableCollateral(collateral);
+  await program.methods
+    .initializeAccessControl()
+    .accountsPartial({ accessControl, admin: authority })
+    .rpc();
+  return collateral;
+};
+
+export const setupPlatform = () => (platform ??= initializePlatform());
+
+// An extra enabled collateral mint on the shared platform, on Token-2022 with
+// a transfer fee when `transferFeeBps` is given
+export const addCollateral = async (decimals: number, transferFeeBps?: number): Promise<Platform> => {
+  await setupPlatform();
+  const collateral = await newMint(decimals, transferFeeBps);
+  await enableCollateral(collateral);
+  return collateral;
+};
+
+export interface Bettor {
+  user: anchor.web3.Keypair;
+  token: anchor.web3.PublicKey;
+}
+
+// A fresh wallet with SOL for rent and `amount` of the platform token
+export const fundedUser = async (
+  { mint, tokenProgram }: Platform,
+  amount: number = 1_000_000_000
+): Promise<Bettor> => {
+  const user = anchor.web3.Keypair.generate();
+  await provider.connection.confirmTransaction(
+    await provider.connection.requestAirdrop(user.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL)
+  );
+  const token = (
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection, payer, mint, user.publicKey, false, undefined, undefined, tokenProgram
+    )
+  ).address;
+  await mintTo(provider.connection, payer, mint, token, payer, amount, [], undefined, tokenProgram);
+  return { user, token };
+};
+
+// Markets default to a one-token minimum bet and a ten-token creator stake,
+// created by the access control admin unless `creator` is given
+export const createMarket = async (
+  { mint, tokenProgram, userToken, decimals }: Platform,
+  id: string,
+  {
+    resolutionDate = Math.floor(Date.now() / 1000) + 7200,
+    stake = new BN(10).pow(new BN(decimals)).muln(10),
+    minBet = new BN(10).pow(new BN(decimals)),
+    creator,
+  }: { resolutionDate?: number; stake?: BN; minBet?: BN; creator?: Bettor } = {}
+) => {
+  const market = anchor.web3.Keypair.generate();
+  const creatorKey = creator?.user.publicKey ?? authority;
+  await program.methods
+    .createMarket(
+      { binary: {} },
+      fixed("Will the fixture go ahead?", 200),
+      26,
+      fixed("Yes", 50),
+      fixed("No", 50),
+      fixed("", 50),
+      fixed("", 50),
+      2,
+      new BN(resolutionDate),
+      new BN(100),
+      minBet,
+      fixed(id, 32),
+      id.length,
+      stake,
+      fixed("sports", 20),
+      6
+    )
+    .accountsPartial({
+      market: market.publicKey,
+      platformState,
+      creator: creatorKey,
+      creatorTokenAccount: creator?.token ?? userToken,
+      marketEscrow: escrowFor(market.publicKey),
+      tokenMint: mint,
+      accessControl,
+      // Whitelisted creators other than the admin create against their config
+      creatorConfig: creator &&
+        (await program.account.creatorConfig.fetchNullable(creatorConfigFor(creatorKey)))
+        ? creatorConfigFor(creatorKey)
+        : null,
+      // The market is new, so its creation burn always uses nonce 0
+      burnReceipt: await burnReceiptOn(mint, market.publicKey, creatorKey, new BN(0)),
+      tokenProgram,
+    })
+    .signers(creator ? [market, creator.user] : [market])
+    .rpc();
+  return market.publicKey;
+};
+
+// The place_prediction call, for bundling with other instructions
+export const placePredictionBuilder = async (
+  { mint, tokenProgram, userToken, treasuryVault, feeLedger }: Platform,
+  market: anchor.web3.PublicKey,
+  option: number,
+  amount: number = 5_000_000,
+  {
+    bettor,
+    referrer,
+    points,
+  }: { bettor?: Bettor; referrer?: anchor.web3.PublicKey; points?: anchor.web3.PublicKey } = {}
+) => {
+  const user = bettor?.user.publicKey ?? authority;
+  const nonce = nextBurnNonce();
+  return program.methods
+    .placePrediction(option, new BN(amount), nonce)
+    .accountsPartial({
+      market,
+      platformState,
+      user,
+      userTokenAccount: bettor?.token ?? userToken,
+      marketEscrow: escrowFor(market),
+      tokenMint: mint,
+      treasuryVault,
+      feeLedger,
+      prediction: predictionFor(market, option, user),
+      pmpTokenAccount: points ?? null,
+      referral: referralFor(user, mint),
+      referrerRewards: referrer ? referrerRewardsFor(referrer, mint) : null,
+      burnReceipt: await burnReceiptOn(mint, market, user, nonce),
+      tokenProgram,
+    })
+    .signers(bettor ? [bettor.user] : []);
+};
+
+export const placePrediction = async (...args: Parameters<typeof placePredictionBuilder>) =>
+  (await placePredictionBuilder(...args)).rpc();
+
+export const claimReward = async (
+  { mint, tokenProgram, userToken, treasuryVault, feeLedger }: Platform,
+  market: anchor.web3.PublicKey,
+  option: number
+) => {
+  const nonce = nextBurnNonce();
+  return program.methods
+    .claimReward(option, nonce)
+    .accountsPartial({
+      market,
+      platformState,
+      prediction: predictionFor(market, option),
+      user: authority,
+      userTokenAccount: userToken,
+      marketEscrow: escrowFor(market),
+      treasuryVault,
+      feeLedger,
+      tokenMint: mint,
+      burnReceipt: await burnReceiptOn(mint, market, authority, nonce),
+      tokenProgram,
+    })
+    .rpc();
+};
+
+export const resolveMarket = async (market: anchor.web3.PublicKey, option: number) =>
+  program.methods
+    .resolveMarket(option)
+    .accountsPartial({ market, resolver: authority, creatorConfig: await quotaConfigOf(market) })
+    .rpc();
+
+export const cancelMarket = async (market: anchor.web3.PublicKey) =>
+  program.methods
+    .cancelMarket()
+    .accountsPartial({ market, authority, creatorConfig: await quotaConfigOf(market) })
+    .rpc();
@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint, getAccount, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  expectError,
+  fundedUser,
+  setupPlatform,
+  Platform,
+} from "./fixtures";
+
+// Providers own the pool pro rata to their LP tokens. The first deposit locks
+// MINIMUM_LIQUIDITY units that can never be redeemed, and only the owner of
+// an LP account can burn from it.
+describe("liquidity pool", () => {
+  let platform: Platform;
+  let usdcMint: anchor.web3.PublicKey;
+  let userUsdc: anchor.web3.PublicKey;
+  let userLp: anchor.web3.PublicKey;
+  const pool = anchor.web3.Keypair.generate();
+
+  const poolPda = (seed: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(seed), pool.publicKey.toBuffer()],
+      program.programId
+    )[0];
+  const poolTokenAccount = poolPda("pool_token_account");
+  const poolUsdcAccount = poolPda("pool_usdc_account");
+  const lpMint = poolPda("pool_lp_mint");
+
+  const accounts = (
+    user = authority,
+    userTokenAccount = platform.userToken,
+    userUsdcAccount = userUsdc,
+    userLpAccount = userLp
+  ) => ({
+    poolState: pool.publicKey,
+    user,
+    userTokenAccount,
+    userUsdcAccount,
+    userLpAccount,
+    poolTokenAccount,
+    poolUsdcAccount,
+    lpMint,
+  });
+
+  const lpBalance = async () => Number((await getAccount(provider.connection, userLp)).amount);
+
+  before(async () => {
+    platform = await setupPlatform();
+    usdcMint = await createMint(provider.connection, payer, authority, null, 6);
+    userUsdc = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, usdcMint, authority)
+    ).address;
+    await mintTo(provider.connection, payer, usdcMint, userUsdc, payer, 1_000_000_000);
+
+    await program.methods
+      .initializeLiquidityPool("liquidity-pool", 30)
+      .accountsPartial({
+        poolState: pool.publicKey,
+        platformState,
+        authority,
+        tokenMint: platform.mint,
+        usdcMint,
+        poolTokenAccount,
+        poolUsdcAccount,
+        lpMint,
+      })
+      .signers([pool])
+      .rpc();
+    userLp = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, lpMint, authority)
+    ).address;
+  });
+
+  it("mints LP for deposits and redeems it pro rata", async () => {
+    await program.methods
+      .addLiquidity(new BN(1_000_000), new BN(4_000_000), new BN(1_999_000))
+      .accountsPartial(accounts())
+      .rpc();
+    assert.equal(await lpBalance(), 1_999_000);
+
+    await program.methods
+      .removeLiquidity(new BN(1_000_000), new BN(500_000), new BN(2_000_000))
+      .accountsPartial(accounts())
+      .rpc();
+    assert.equal(await lpBalance(), 999_000);
+
+    const state = await program.account.liquidityPoolState.fetch(pool.publicKey);
+    assert.equal(state.tokenReserve.toNumber(), 500_000);
+    assert.equal(state.usdcReserve.toNumber(), 2_000_000);
+    assert.equal(state.lpSupply.toNumber(), 1_000_000);
+    const vault = await getAccount(provider.connection, poolTokenAccount);
+    assert.equal(Number(vault.amount), 500_000);
+  });
+
+  it("never redeems the locked minimum liquidity", async () => {
+    await expectError(
+      program.methods
+        .removeLiquidity(new BN((await lpBalance()) + 1), new BN(0), new BN(0))
+        .accountsPartial(accounts())
+        .rpc(),
+      "InsufficientLiquidity"
+    );
+  });
+
+  it("rejects burning another provider's LP tokens", async () => {
+    const other = await fundedUser(platform);
+    const otherUsdc = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, usdcMint, other.user.publicKey)
+    ).address;
+
+    await expectError(
+      program.methods
+        .removeLiquidity(new BN(1_000), new BN(0), new BN(0))
+        .accountsPartial(accounts(other.user.publicKey, other.token, otherUsdc, userLp))
+        .signers([other.user])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+});
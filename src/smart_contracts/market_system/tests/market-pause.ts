@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  expectError,
+  escrowFor,
+  predictionFor,
+  claimReward,
+  setupPlatform,
+  createMarket as createFixtureMarket,
+  placePrediction as placeFixturePrediction,
+  Platform,
+} from "./fixtures";
+
+// Per-market pause: a paused market rejects new predictions, cannot be
+// claimed or refunded while paused, and can still be resolved or cancelled
+// by the market authority, after which claims and refunds work normally.
+describe("per-market pause", () => {
+  let platform: Platform;
+  let userToken: anchor.web3.PublicKey;
+
+  const createMarket = (id: string) => createFixtureMarket(platform, id);
+  const placePrediction = (market: anchor.web3.PublicKey, option: number) =>
+    placeFixturePrediction(platform, market, option);
+
+  before(async () => {
+    platform = await setupPlatform();
+    ({ userToken } = platform);
+  });
+
+  it("blocks predictions only while paused", async () => {
+    const market = await createMarket("pause-1");
+
+    await program.methods.pauseMarket().accounts({ market, authority }).rpc();
+    await expectError(placePrediction(market, 0), "MarketNotActive");
+
+    await program.methods.unpauseMarket().accounts({ market, authority }).rpc();
+    await placePrediction(market, 0);
+
+    const account = await program.account.market.fetch(market);
+    assert.ok(account.status.active);
+  });
+
+  it("does not affect other markets", async () => {
+    const paused = await createMarket("pause-2");
+    const open = await createMarket("pause-3");
+
+    await program.methods.pauseMarket().accounts({ market: paused, authority }).rpc();
+    await placePrediction(open, 1);
+  });
+
+  it("rejects pause from anyone but the market authority", async () => {
+    const market = await createMarket("pause-4");
+    const stranger = anchor.web3.Keypair.generate();
+
+    await expectError(
+      program.methods
+        .pauseMarket()
+        .accounts({ market, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("allows resolving a paused market and then claiming", async () => {
+    const market = await createMarket("pause-5");
+    await placePrediction(market, 0);
+    await program.methods.pauseMarket().accounts({ market, authority }).rpc();
+
+    await expectError(claimReward(platform, market, 0), "MarketNotResolved");
+
+    await program.methods
+      .resolveMarket(0)
+      .accountsPartial({ market, resolver: authority, creatorConfig: null })
+      .rpc();
+    await claimReward(platform, market, 0);
+  });
+
+  it("allows cancelling a paused market and then refunding", async () => {
+    const market = await createMarket("pause-6");
+    await placePrediction(market, 1);
+    await program.methods.pauseMarket().accounts({ market, authority }).rpc();
+
+    const refund = () =>
+      program.methods
+        .emergencyWithdraw(1)
+        .accountsPartial({
+          market,
+          prediction: predictionFor(market, 1),
+          user: authority,
+          userTokenAccount: userToken,
+          marketEscrow: escrowFor(market),
+          tokenMint: platform.mint,
+          tokenProgram: platform.tokenProgram,
+        })
+        .rpc();
+
+    await expectError(refund(), "MarketNotCancelled");
+
+    await program.methods
+      .cancelMarket()
+      .accountsPartial({ market, authority, creatorConfig: null })
+      .rpc();
+
+    const before = (await getAccount(provider.connection, userToken)).amount;
+    await refund();
+    const after = (await getAccount(provider.connection, userToken)).amount;
+    assert.isTrue(after > before);
+  });
+});
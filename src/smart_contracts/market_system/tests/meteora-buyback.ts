@@ -0,0 +1,213 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { getMint } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  eventsIn,
+  collateralConfigFor,
+  fundedUser,
+  burnReceiptFor,
+  nextBurnNonce,
+  setupPlatform,
+  addCollateral,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// buyback_and_burn against the mock Meteora program, which Anchor.toml loads
+// at the Dynamic AMM's program id, so the real CPI layout is exercised. Both
+// vaults hold 100 tokens, so 1 token in quotes about 0.99 tokens out before
+// the pool's 0.25% fee. The pool is pinned for the input mint with a floor of
+// 0.98 platform tokens per input token.
+describe("meteora buyback", () => {
+  const mock = anchor.workspace.mockMeteora as Program<any>;
+  const vaultProgram = new anchor.web3.PublicKey("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+  const pool = anchor.web3.Keypair.generate();
+  const protocolTokenFee = anchor.web3.Keypair.generate().publicKey;
+  let platform: Platform;
+  let input: Platform;
+
+  const mockPda = (...seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, mock.programId)[0];
+
+  const vaultFor = (mint: anchor.web3.PublicKey) => {
+    const vault = mockPda(Buffer.from("vault"), mint.toBuffer());
+    return {
+      vault,
+      tokenVault: mockPda(Buffer.from("token_vault"), vault.toBuffer()),
+      lpMint: mockPda(Buffer.from("lp_mint"), vault.toBuffer()),
+      poolLp: mockPda(vault.toBuffer(), pool.publicKey.toBuffer()),
+    };
+  };
+
+  const meteoraAccounts = () => {
+    const a = vaultFor(platform.mint);
+    const b = vaultFor(input.mint);
+    return {
+      pool: pool.publicKey,
+      aVault: a.vault,
+      bVault: b.vault,
+      aTokenVault: a.tokenVault,
+      bTokenVault: b.tokenVault,
+      aVaultLpMint: a.lpMint,
+      bVaultLpMint: b.lpMint,
+      aVaultLp: a.poolLp,
+      bVaultLp: b.poolLp,
+      protocolTokenFee,
+      vaultProgram,
+      ammProgram: mock.programId,
+    };
+  };
+
+  const buyback = (amountIn: number, minimumOut: number, caller?: anchor.web3.Keypair) => {
+    const nonce = nextBurnNonce();
+    const callerKey = caller?.publicKey ?? authority;
+    return program.methods
+      .buybackAndBurn(new BN(amountIn), new BN(minimumOut), nonce)
+      .accountsPartial({
+        platformState,
+        caller: callerKey,
+        inputMint: input.mint,
+        inputVault: input.treasuryVault,
+        inputFeeLedger: input.feeLedger,
+        tokenMint: platform.mint,
+        treasuryVault: platform.treasuryVault,
+        collateralConfig: collateralConfigFor(platform.mint),
+        meteora: meteoraAccounts(),
+        burnReceipt: burnReceiptFor(anchor.web3.PublicKey.default, callerKey, nonce),
+        tokenProgram: platform.tokenProgram,
+        inputTokenProgram: input.tokenProgram,
+      })
+      .signers(caller ? [caller] : [])
+      .rpc();
+  };
+
+  const MIN_PRICE = 980_000;
+
+  const setBuybackPool = (
+    poolKey: anchor.web3.PublicKey = pool.publicKey,
+    minPrice: number = MIN_PRICE,
+    inputMint: anchor.web3.PublicKey = input.mint
+  ) =>
+    program.methods
+      .setBuybackPool(inputMint, poolKey, new BN(minPrice))
+      .accountsPartial({ platformState, authority })
+      .rpc();
+
+  const seedVault = async ({ mint, userToken }: Platform, amount: number) => {
+    const { vault, tokenVault, lpMint, poolLp } = vaultFor(mint);
+    await mock.methods
+      .deposit(new BN(amount))
+      .accountsPartial({ vault, tokenVault, lpMint, poolLp, depositorToken: userToken, depositor: authority })
+      .rpc();
+  };
+
+  before(async () => {
+    platform = await setupPlatform();
+    input = await addCollateral(6);
+
+    // Accrue treasury fees in the input mint
+    const market = await createMarket(input, "buyback-1");
+    await placePrediction(input, market, 0, 50_000_000);
+
+    for (const mint of [platform.mint, input.mint]) {
+      await mock.methods.initializeVault().accountsPartial({ tokenMint: mint, payer: authority }).rpc();
+    }
+    const a = vaultFor(platform.mint);
+    const b = vaultFor(input.mint);
+    await mock.methods
+      .initializePool(25)
+      .accountsPartial({
+        pool: pool.publicKey,
+        aVault: a.vault,
+        bVault: b.vault,
+        aVaultLpMint: a.lpMint,
+        bVaultLpMint: b.lpMint,
+        aVaultLp: a.poolLp,
+        bVaultLp: b.poolLp,
+        payer: authority,
+      })
+      .signers([pool])
+      .rpc();
+    await seedVault(platform, 100_000_000);
+    await seedVault(input, 100_000_000);
+    await setBuybackPool();
+  });
+
+  it("swaps accrued fees through the pool and burns the output", async () => {
+    const supplyBefore = (await getMint(provider.connection, platform.mint)).supply;
+    const ledgerBefore = await program.account.feeLedger.fetch(input.feeLedger);
+
+    const events = await eventsIn(await buyback(1_000_000, 985_000));
+
+    // 100 x 100 tokens in the pool and a 0.25% trade fee on the 1 token in
+    const supplyAfter = (await getMint(provider.connection, platform.mint)).supply;
+    assert.equal(Number(supplyBefore - supplyAfter), 987_648);
+    const ledgerAfter = await program.account.feeLedger.fetch(input.feeLedger);
+    assert.equal(ledgerBefore.treasuryAccrued.sub(ledgerAfter.treasuryAccrued).toNumber(), 1_000_000);
+
+    const executed = events.find((event) => event.name.toLowerCase() === "buybackexecuted").data as any;
+    assert.ok(executed.pool.equals(pool.publicKey));
+    assert.ok(executed.inputMint.equals(input.mint));
+    assert.equal(executed.amountIn.toNumber(), 1_000_000);
+    assert.equal(executed.burnAmount.toNumber(), 987_648);
+    const burn = events.find((event) => event.name.toLowerCase() === "burnevent").data as any;
+    assert.ok(burn.burnType.buyback);
+    assert.equal(burn.burnAmount.toNumber(), 987_648);
+  });
+
+  it("only lets the authority or the buyback crank trigger it", async () => {
+    const stranger = await fundedUser(platform);
+    await expectError(buyback(1_000_000, 0, stranger.user), "Unauthorized");
+  });
+
+  it("rejects a minimum output below the pinned price", async () => {
+    await expectError(buyback(1_000_000, 0), "BuybackMinimumTooLow");
+    await expectError(buyback(1_000_000, 979_999), "BuybackMinimumTooLow");
+  });
+
+  it("follows the authority's price floor", async () => {
+    await setBuybackPool(pool.publicKey, 900_000);
+    try {
+      await buyback(1_000_000, 900_000);
+      const state = await program.account.platformState.fetch(platformState);
+      const pinned = (state.buybackPools as any[]).find((entry) => entry.inputMint.equals(input.mint));
+      assert.equal(pinned.minPrice.toNumber(), 900_000);
+    } finally {
+      await setBuybackPool();
+    }
+    await expectError(buyback(1_000_000, 900_000), "BuybackMinimumTooLow");
+  });
+
+  it("only swaps through the pool pinned for the input mint", async () => {
+    await setBuybackPool(anchor.web3.Keypair.generate().publicKey);
+    try {
+      await expectError(buyback(1_000_000, MIN_PRICE), "BuybackPoolNotSet");
+    } finally {
+      await setBuybackPool();
+    }
+
+    // Unpinning the mint stops its buybacks altogether
+    await setBuybackPool(anchor.web3.PublicKey.default);
+    try {
+      await expectError(buyback(1_000_000, MIN_PRICE), "BuybackPoolNotSet");
+    } finally {
+      await setBuybackPool();
+    }
+  });
+
+  it("validates pinned pools", async () => {
+    await expectError(setBuybackPool(pool.publicKey, 0), "InvalidBuybackPool");
+    await expectError(setBuybackPool(pool.publicKey, MIN_PRICE, platform.mint), "InvalidMint");
+  });
+
+  it("passes the minimum output through to the pool", async () => {
+    await expectError(buyback(1_000_000, 1_000_000), "ExceededSlippage");
+  });
+});
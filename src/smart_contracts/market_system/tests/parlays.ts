@@ -0,0 +1,207 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  eventsIn,
+  collateralConfigFor,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  placePredictionBuilder,
+  cancelMarket,
+  Platform,
+} from "./fixtures";
+
+// Parlays lock each leg at the market's parimutuel odds net of claim fees,
+// ignoring bets from the same slot and capped by the house, and are paid from
+// the house, which reserves every open parlay's payout.
+describe("parlays", () => {
+  let platform: Platform;
+  let legs: anchor.web3.PublicKey[];
+  let parlayHouse: anchor.web3.PublicKey;
+  let parlayVault: anchor.web3.PublicKey;
+  let nextParlayId = 0;
+
+  const pda = (...seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const parlayFor = (id: BN) =>
+    pda(Buffer.from("parlay"), authority.toBuffer(), id.toArrayLike(Buffer, "le", 8));
+
+  const placeParlay = async (
+    stake: number,
+    minimumPayout = 0,
+    markets = legs,
+    preInstructions: anchor.web3.TransactionInstruction[] = []
+  ) => {
+    const id = new BN(++nextParlayId);
+    const signature = await program.methods
+      .placeParlay(id, new BN(stake), Buffer.from(markets.map(() => 0)), new BN(minimumPayout))
+      .accountsPartial({
+        platformState,
+        user: authority,
+        userTokenAccount: platform.userToken,
+        parlayHouse,
+        parlayVault,
+        parlay: parlayFor(id),
+        tokenMint: platform.mint,
+        collateralConfig: collateralConfigFor(platform.mint),
+        tokenProgram: platform.tokenProgram,
+      })
+      .remainingAccounts(markets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .preInstructions(preInstructions)
+      .rpc();
+    return { parlay: parlayFor(id), signature };
+  };
+
+  const settle = (parlay: anchor.web3.PublicKey) =>
+    program.methods
+      .settleParlay()
+      .accountsPartial({
+        platformState,
+        parlay,
+        parlayHouse,
+        parlayVault,
+        userTokenAccount: platform.userToken,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .remainingAccounts(legs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .rpc();
+
+  const close = (parlay: anchor.web3.PublicKey) =>
+    program.methods.closeParlay().accountsPartial({ parlay, user: authority }).rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+    parlayHouse = pda(Buffer.from("parlay_house"), platform.mint.toBuffer());
+    parlayVault = pda(Buffer.from("parlay_vault"), platform.mint.toBuffer());
+    await program.methods
+      .initializeParlayHouse(500, new BN(30_000), new BN(50_000), new BN(5), 2)
+      .accountsPartial({
+        platformState,
+        authority,
+        collateralConfig: collateralConfigFor(platform.mint),
+        parlayHouse,
+        parlayVault,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+    await program.methods
+      .fundParlayHouse(new BN(10_000_000))
+      .accountsPartial({
+        platformState,
+        authority,
+        parlayHouse,
+        parlayVault,
+        authorityTokenAccount: platform.userToken,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+    // One token on the leg option against nine on the other: 10x, capped at 5x per leg
+    legs = [];
+    for (const id of ["parlay-leg-1", "parlay-leg-2"]) {
+      const market = await createMarket(platform, id);
+      await placePrediction(platform, market, 0, 1_000_000);
+      await placePrediction(platform, market, 1, 9_000_000);
+      legs.push(market);
+    }
+  });
+
+  it("locks the leg odds and reserves the capped payout", async () => {
+    const { parlay, signature } = await placeParlay(1_000_000);
+    const placed = (await eventsIn(signature)).find(
+      (event) => event.name.toLowerCase() === "parlayplaced"
+    ).data as any;
+
+    const account = await program.account.parlay.fetch(parlay);
+    for (const leg of account.legs) {
+      assert.equal(leg.oddsBps.toNumber(), 50_000);
+    }
+    assert.equal(account.expiresAt.toNumber(), placed.expiresAt.toNumber());
+    // 25x less the 5% edge, capped at the house's 3x
+    assert.equal(account.payout.toNumber(), 3_000_000);
+    assert.equal(placed.payout.toNumber(), 3_000_000);
+    const house = await program.account.parlayHouse.fetch(parlayHouse);
+    assert.equal(house.balance.toNumber(), 11_000_000);
+    assert.equal(house.reserved.toNumber(), 3_000_000);
+    assert.equal(house.openParlays, 1);
+  });
+
+  it("refuses parlays the house can't cover or below the minimum payout", async () => {
+    await expectError(placeParlay(1_000_000, 3_000_001), "SlippageTooHigh");
+    await expectError(placeParlay(5_000_000), "InsufficientHouseLiquidity");
+  });
+
+  it("refuses legs on markets below the house's minimum pool", async () => {
+    const thin = await createMarket(platform, "parlay-thin");
+    await placePrediction(platform, thin, 0, 1_000_000);
+    await placePrediction(platform, thin, 1, 1_000_000);
+    await expectError(placeParlay(1_000_000, 0, [legs[0], thin]), "ParlayLegTooThin");
+  });
+
+  it("refuses legs on markets with fewer bettors than the house's minimum", async () => {
+    const lonely = await createMarket(platform, "parlay-lonely");
+    await placePrediction(platform, lonely, 0, 10_000_000);
+    await expectError(placeParlay(1_000_000, 0, [legs[0], lonely]), "ParlayLegTooThin");
+  });
+
+  it("prices legs net of claim fees and ignores bets from the same slot", async () => {
+    const swing = await createMarket(platform, "parlay-swing");
+    await placePrediction(platform, swing, 0, 4_000_000);
+    await placePrediction(platform, swing, 1, 6_000_000);
+
+    // Pool less the creator fee and claim burn, over the leg option's stake
+    const market = await program.account.market.fetch(swing);
+    const state = await program.account.platformState.fetch(platformState);
+    const collateral = await program.account.collateralConfig.fetch(collateralConfigFor(platform.mint));
+    const claimFeeRate = market.creatorFeeRate.add(collateral.burnEnabled ? state.claimBurnRate : new BN(0));
+    const expected = market.totalPool
+      .mul(new BN(10_000).sub(claimFeeRate))
+      .divn(10_000)
+      .muln(10_000)
+      .div(market.option1Pool)
+      .toNumber();
+    assert.isBelow(expected, 30_000);
+
+    // Piling onto the other side in the parlay's own transaction doesn't pay
+    const pile = await placePredictionBuilder(platform, swing, 1, 20_000_000);
+    const { parlay } = await placeParlay(100_000, 0, [legs[0], swing], [await pile.instruction()]);
+    const locked = await program.account.parlay.fetch(parlay);
+    assert.equal(locked.legs[1].oddsBps.toNumber(), expected);
+
+    // Once the slot has passed, the bet is priced in
+    const { parlay: later } = await placeParlay(100_000, 0, [legs[0], swing]);
+    const repriced = await program.account.parlay.fetch(later);
+    assert.equal(repriced.legs[1].oddsBps.toNumber(), 50_000);
+  });
+
+  it("returns the stake when every leg is cancelled and closes once settled", async () => {
+    const { parlay } = await placeParlay(1_000_000);
+    await expectError(settle(parlay), "ParlayNotSettleable");
+    await expectError(close(parlay), "ParlayNotSettled");
+
+    for (const market of legs) {
+      await cancelMarket(market);
+    }
+    const before = await getAccount(provider.connection, platform.userToken);
+    await settle(parlay);
+
+    const after = await getAccount(provider.connection, platform.userToken);
+    assert.equal(Number(after.amount - before.amount), 1_000_000);
+    const settled = await program.account.parlay.fetch(parlay);
+    assert.isTrue(settled.settled);
+    assert.equal(settled.paidOut.toNumber(), 1_000_000);
+
+    await close(parlay);
+    assert.isNull(await provider.connection.getAccountInfo(parlay));
+  });
+});
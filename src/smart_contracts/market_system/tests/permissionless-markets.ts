@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  escrowFor,
+  setupPlatform,
+  fundedUser,
+  createMarket,
+  placePrediction,
+  resolveMarket,
+  Bettor,
+  Platform,
+} from "./fixtures";
+
+// Permissionless creation: a wallet outside the whitelist posts its stake as a
+// bond, the market waits in PendingReview until the market authority approves
+// or rejects it, and an approved market's bond goes back to the creator once
+// the market is finalized.
+describe("permissionless market creation", () => {
+  const BOND = 20_000_000;
+  // create_market burns 0.5% of the stake and escrows the rest as the bond
+  const HELD = BOND - BOND * 50 / 10_000;
+
+  let platform: Platform;
+  let creator: Bettor;
+
+  const balance = async (account: anchor.web3.PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const createBonded = (id: string, stake: number = BOND) =>
+    createMarket(platform, id, { stake: new BN(stake), creator });
+
+  const approve = (market: anchor.web3.PublicKey, signer?: anchor.web3.Keypair) =>
+    program.methods
+      .approveMarket()
+      .accounts({ market, authority: signer?.publicKey ?? authority })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  const reject = (market: anchor.web3.PublicKey, slashBond: boolean) =>
+    program.methods
+      .rejectMarket(slashBond)
+      .accountsPartial({
+        market,
+        platformState,
+        authority,
+        marketEscrow: escrowFor(market),
+        creatorTokenAccount: creator.token,
+        treasuryVault: platform.treasuryVault,
+        feeLedger: platform.feeLedger,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  const release = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .releaseCreatorBond()
+      .accountsPartial({
+        market,
+        creator: creator.user.publicKey,
+        creatorTokenAccount: creator.token,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .signers([creator.user])
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+    creator = await fundedUser(platform);
+    await program.methods
+      .setPermissionlessCreation(true, new BN(BOND))
+      .accountsPartial({ platformState, authority })
+      .rpc();
+  });
+
+  after(async () => {
+    await program.methods
+      .setPermissionlessCreation(false, new BN(0))
+      .accountsPartial({ platformState, authority })
+      .rpc();
+  });
+
+  it("escrows the stake as a bond and holds the market for review", async () => {
+    const before = await balance(creator.token);
+    const market = await createBonded("bond-1");
+
+    const account = await program.account.market.fetch(market);
+    assert.ok(account.status.pendingReview);
+    assert.equal(account.creatorBond.toNumber(), HELD);
+    assert.equal(await balance(escrowFor(market)), HELD);
+    assert.equal(before - (await balance(creator.token)), BOND);
+
+    await expectError(createBonded("bond-2", BOND - 1), "BondTooLow");
+  });
+
+  it("takes bets only after the market authority approves", async () => {
+    const market = await createBonded("bond-3");
+    await expectError(placePrediction(platform, market, 0), "MarketNotActive");
+
+    // The creator can't approve their own market
+    await expectError(approve(market, creator.user), "Unauthorized");
+
+    await approve(market);
+    await placePrediction(platform, market, 0);
+    assert.ok((await program.account.market.fetch(market)).status.active);
+    await expectError(approve(market), "MarketNotPendingReview");
+  });
+
+  it("refunds the bond on rejection, or slashes it to the treasury vault", async () => {
+    const refunded = await createBonded("bond-4");
+    const creatorBefore = await balance(creator.token);
+    await reject(refunded, false);
+    assert.equal((await balance(creator.token)) - creatorBefore, HELD);
+
+    const slashed = await createBonded("bond-5");
+    const treasuryBefore = await balance(platform.treasuryVault);
+    await reject(slashed, true);
+    assert.equal((await balance(platform.treasuryVault)) - treasuryBefore, HELD);
+
+    for (const market of [refunded, slashed]) {
+      const account = await program.account.market.fetch(market);
+      assert.ok(account.status.cancelled);
+      assert.equal(account.creatorBond.toNumber(), 0);
+      assert.equal(await balance(escrowFor(market)), 0);
+    }
+  });
+
+  it("releases an approved market's bond once it is resolved", async () => {
+    const market = await createBonded("bond-6");
+    await approve(market);
+    await expectError(release(market), "MarketNotFinalized");
+
+    await resolveMarket(market, 0);
+    const before = await balance(creator.token);
+    await release(market);
+    assert.equal((await balance(creator.token)) - before, HELD);
+
+    // The bond is paid out once
+    await expectError(release(market), "InvalidAmount");
+  });
+});
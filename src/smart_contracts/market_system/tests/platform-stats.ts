@@ -0,0 +1,62 @@
+import { assert } from "chai";
+import {
+  program,
+  platformState,
+  expectError,
+  collateralConfigFor,
+  setupPlatform,
+  addCollateral,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Bets only write their mint's CollateralConfig; the platform totals catch up
+// through sync_platform_stats.
+describe("platform stats", () => {
+  let platform: Platform;
+
+  const sync = () =>
+    program.methods
+      .syncPlatformStats()
+      .accountsPartial({ platformState, collateralConfig: collateralConfigFor(platform.mint) })
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("counts bets per mint and leaves the platform state alone", async () => {
+    const market = await createMarket(platform, "stats-1");
+    const stateBefore = await program.account.platformState.fetch(platformState);
+    const configBefore = await program.account.collateralConfig.fetch(collateralConfigFor(platform.mint));
+
+    await placePrediction(platform, market, 0, 5_000_000);
+
+    const stateAfter = await program.account.platformState.fetch(platformState);
+    const configAfter = await program.account.collateralConfig.fetch(collateralConfigFor(platform.mint));
+    assert.equal(stateAfter.totalVolume.toString(), stateBefore.totalVolume.toString());
+    assert.equal(configAfter.totalVolume.sub(configBefore.totalVolume).toNumber(), 5_000_000);
+    assert.isTrue(configAfter.burnSequence.gt(configBefore.burnSequence));
+  });
+
+  it("rolls the platform token totals up on sync", async () => {
+    await sync();
+
+    const state = await program.account.platformState.fetch(platformState);
+    const config = await program.account.collateralConfig.fetch(collateralConfigFor(platform.mint));
+    assert.equal(state.totalVolume.toString(), config.totalVolume.toString());
+    assert.equal(state.totalBurned.toString(), config.totalBurned.toString());
+  });
+
+  it("only syncs from the platform token's config", async () => {
+    const other = await addCollateral(6);
+    await expectError(
+      program.methods
+        .syncPlatformStats()
+        .accountsPartial({ platformState, collateralConfig: collateralConfigFor(other.mint) })
+        .rpc(),
+      "ConstraintSeeds"
+    );
+  });
+});
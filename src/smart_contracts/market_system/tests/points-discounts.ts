@@ -0,0 +1,72 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  eventsIn,
+  setCollateralConfig,
+  addCollateral,
+  fundedUser,
+  createMarket,
+  placePrediction,
+} from "./fixtures";
+
+// PMP points holders get part of the platform fee waived. The discount comes
+// off the rate left after the volume tier and the collateral's fee cap; the
+// burn is never discounted. The platform charges a 1% fee and 2.5% burn.
+describe("points discounts", () => {
+  const setFeeTiers = (tiers: object[]) =>
+    program.methods.setFeeTiers(tiers).accountsPartial({ platformState, authority }).rpc();
+
+  const setPointsDiscounts = (pmpMint: anchor.web3.PublicKey, tiers: object[]) =>
+    program.methods.setPointsDiscounts(pmpMint, tiers).accountsPartial({ platformState, authority }).rpc();
+
+  const placed = async (signature: string) =>
+    (await eventsIn(signature)).find((event) => event.name.toLowerCase() === "predictionplaced")
+      .data as any;
+
+  after(async () => {
+    await setFeeTiers([]);
+    await setPointsDiscounts(anchor.web3.PublicKey.default, []);
+  });
+
+  it("discounts the fee after the volume tier and the collateral cap", async () => {
+    const collateral = await addCollateral(6);
+    await setCollateralConfig(collateral.mint, { platformFeeRate: 80 });
+    await setFeeTiers([
+      { minVolume: new BN(10), platformFeeRate: new BN(60), betBurnRate: new BN(100) },
+    ]);
+
+    const bettor = await fundedUser(collateral);
+    const pmpMint = await createMint(provider.connection, payer, authority, null, 0);
+    const points = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, pmpMint, bettor.user.publicKey)
+    ).address;
+    await mintTo(provider.connection, payer, pmpMint, points, payer, 1_000);
+    await setPointsDiscounts(pmpMint, [{ minBalance: new BN(1_000), discountBps: 5_000 }]);
+
+    const market = await createMarket(collateral, "points-1");
+
+    // Base 1% capped at 0.8% by the collateral, then half off
+    const first = await placed(
+      await placePrediction(collateral, market, 0, 10_000_000, { bettor, points })
+    );
+    assert.equal(first.pointsDiscountBps, 5_000);
+    assert.equal(first.platformFeeRate.toNumber(), 40);
+    assert.equal(first.platformFee.toNumber(), 40_000);
+    assert.equal(first.betBurnRate.toNumber(), 250);
+
+    // The 0.6% volume tier is under the cap, then half off
+    const second = await placed(
+      await placePrediction(collateral, market, 0, 10_000_000, { bettor, points })
+    );
+    assert.equal(second.platformFeeRate.toNumber(), 30);
+    assert.equal(second.platformFee.toNumber(), 30_000);
+    assert.equal(second.burnAmount.toNumber(), 100_000);
+  });
+});
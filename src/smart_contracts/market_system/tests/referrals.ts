@@ -0,0 +1,87 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program,
+  expectError,
+  referralFor,
+  referrerRewardsFor,
+  fundedUser,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  Bettor,
+  Platform,
+} from "./fixtures";
+
+// A referrer is bound on the user's first bet and can neither be changed,
+// left out of a later bet, nor attached to a user who has already bet
+// without one.
+describe("referrals", () => {
+  let platform: Platform;
+  let market: anchor.web3.PublicKey;
+  let referrerA: Bettor;
+  let referrerB: Bettor;
+
+  const register = (referrer: Bettor) =>
+    program.methods
+      .registerReferrer()
+      .accountsPartial({
+        referrer: referrer.user.publicKey,
+        referrerRewards: referrerRewardsFor(referrer.user.publicKey, platform.mint),
+        tokenMint: platform.mint,
+      })
+      .signers([referrer.user])
+      .rpc();
+
+  const bet = (bettor: Bettor, referrer?: Bettor) =>
+    placePrediction(platform, market, 0, 5_000_000, {
+      bettor,
+      referrer: referrer?.user.publicKey,
+    });
+
+  before(async () => {
+    platform = await setupPlatform();
+    market = await createMarket(platform, "referral-1");
+    referrerA = await fundedUser(platform);
+    referrerB = await fundedUser(platform);
+    await register(referrerA);
+    await register(referrerB);
+  });
+
+  it("binds the referrer on the first bet", async () => {
+    const user = await fundedUser(platform);
+    await bet(user, referrerA);
+    await bet(user, referrerA);
+
+    const referral = await program.account.referral.fetch(referralFor(user.user.publicKey, platform.mint));
+    assert.ok(referral.referrer.equals(referrerA.user.publicKey));
+    const rewards = await program.account.referrerRewards.fetch(
+      referrerRewardsFor(referrerA.user.publicKey, platform.mint)
+    );
+    assert.equal(rewards.referralCount, 1);
+  });
+
+  it("rejects self-referral", async () => {
+    const user = await fundedUser(platform);
+    await register(user);
+    await expectError(bet(user, user), "SelfReferral");
+  });
+
+  it("rejects changing the referrer", async () => {
+    const user = await fundedUser(platform);
+    await bet(user, referrerA);
+    await expectError(bet(user, referrerB), "ReferrerChangeNotAllowed");
+  });
+
+  it("requires the bound referrer on every later bet", async () => {
+    const user = await fundedUser(platform);
+    await bet(user, referrerA);
+    await expectError(bet(user), "ReferrerRewardsRequired");
+  });
+
+  it("rejects binding a referrer after an unreferred bet", async () => {
+    const user = await fundedUser(platform);
+    await bet(user);
+    await expectError(bet(user, referrerA), "ReferrerChangeNotAllowed");
+  });
+});
@@ -1,85 +0,0 @@
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::clock::Clock;
-use anchor_lang::solana_program::hash::hash;
-use anchor_lang::solana_program::program_pack::Pack;
-use anchor_lang::solana_program::system_instruction;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use solana_program_test::{*};
-use solana_sdk::{
-    account::Account,
-    pubkey::Pubkey,
-    signature::{Keypair, Signer},
-    transaction::Transaction,
-};
-
-use market_system::*;
-
-#[tokio::test]
-async fn test_option_index_bounds() {
-    let program_test = ProgramTest::new(
-        "market_system",
-        market_system::id(),
-        processor!(market_system::entry),
-    );
-    
-    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-    
-    // Test placing bet with invalid option index should fail
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_bet_amount_limits() {
-    // Test that bets below minimum fail
-    // Test that bets above maximum fail
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_pool_overflow_protection() {
-    // Test that pool cannot exceed MAX_TOTAL_POOL_SIZE
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_division_by_zero_protection() {
-    // Test claim_reward with zero winning pool
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_arithmetic_overflow_protection() {
-    // Test large number multiplications don't overflow
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_fee_rate_limits() {
-    // Test that fee rates cannot exceed maximum
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_market_duration_limits() {
-    // Test minimum and maximum market duration
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_double_claim_prevention() {
-    // Test that rewards cannot be claimed twice
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_authority_checks() {
-    // Test only authority can resolve markets
-    // Test only whitelisted creators can create markets
-    // TODO: Implement full test
-}
-
-#[tokio::test]
-async fn test_reentrancy_protection() {
-    // Test that state is updated before transfers
-    // TODO: Implement full test
-} 
\ No newline at end of file
@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  authority,
+  platformState,
+  expectError,
+  escrowFor,
+  collateralConfigFor,
+  sponsorshipFor,
+  setupPlatform,
+  createMarket,
+  cancelMarket,
+  Platform,
+} from "./fixtures";
+
+// Sponsors seed option pools and add a bonus for winners. The subsidy stays
+// in the escrow until the market is cancelled or nobody backs the winner.
+describe("market sponsorship", () => {
+  let platform: Platform;
+
+  const sponsor = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .sponsorMarket(new BN(2_000_000), [new BN(1_000_000), new BN(500_000), new BN(0), new BN(0)])
+      .accountsPartial({
+        market,
+        platformState,
+        sponsor: authority,
+        sponsorTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        sponsorship: sponsorshipFor(market, authority),
+        collateralConfig: collateralConfigFor(platform.mint),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  const withdraw = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .withdrawSponsorship()
+      .accountsPartial({
+        market,
+        platformState,
+        sponsor: authority,
+        sponsorship: sponsorshipFor(market, authority),
+        sponsorTokenAccount: platform.userToken,
+        marketEscrow: escrowFor(market),
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("seeds the option pools and books the bonus", async () => {
+    const market = await createMarket(platform, "sponsor-1");
+    await sponsor(market);
+
+    const account = await program.account.market.fetch(market);
+    assert.equal(account.option1Pool.toNumber(), 1_000_000);
+    assert.equal(account.option2Pool.toNumber(), 500_000);
+    assert.equal(account.subsidyBonus.toNumber(), 2_000_000);
+    assert.equal(account.subsidyTotal.toNumber(), 3_500_000);
+    const sponsorship = await program.account.sponsorship.fetch(sponsorshipFor(market, authority));
+    assert.equal(sponsorship.seedAmount.toNumber(), 1_500_000);
+    assert.equal(sponsorship.bonusAmount.toNumber(), 2_000_000);
+  });
+
+  it("keeps the subsidy in the escrow until the market is cancelled", async () => {
+    const market = await createMarket(platform, "sponsor-2");
+    await sponsor(market);
+    await expectError(withdraw(market), "SubsidyNotRefundable");
+
+    await cancelMarket(market);
+    const before = await getAccount(provider.connection, platform.userToken);
+    await withdraw(market);
+
+    const after = await getAccount(provider.connection, platform.userToken);
+    assert.equal(Number(after.amount - before.amount), 3_500_000);
+    const sponsorship = await program.account.sponsorship.fetch(sponsorshipFor(market, authority));
+    assert.isTrue(sponsorship.refunded);
+  });
+});
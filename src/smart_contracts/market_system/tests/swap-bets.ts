@@ -0,0 +1,178 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  expectError,
+  eventsIn,
+  escrowFor,
+  predictionFor,
+  referralFor,
+  collateralConfigFor,
+  userStatsFor,
+  burnReceiptFor,
+  nextBurnNonce,
+  setupPlatform,
+  createMarket,
+  Platform,
+} from "./fixtures";
+
+// A swap bet pays another mint into the integrated pool and bets the
+// collateral that comes out, all in one transaction.
+describe("swap bets", () => {
+  let platform: Platform;
+  let market: anchor.web3.PublicKey;
+  let usdcMint: anchor.web3.PublicKey;
+  let userUsdc: anchor.web3.PublicKey;
+  const pool = anchor.web3.Keypair.generate();
+
+  const poolPda = (seed: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(seed), pool.publicKey.toBuffer()],
+      program.programId
+    )[0];
+  const poolTokenAccount = poolPda("pool_token_account");
+  const poolUsdcAccount = poolPda("pool_usdc_account");
+  const lpMint = poolPda("pool_lp_mint");
+
+  const balance = async (account: anchor.web3.PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const swapBet = (
+    amountIn: number,
+    minimumOut: number,
+    { inputMint = usdcMint, userInputAccount = userUsdc } = {}
+  ) => {
+    const nonce = nextBurnNonce();
+    return program.methods
+      .placePredictionWithSwap(0, new BN(amountIn), nonce, new BN(minimumOut))
+      .accountsPartial({
+        bet: {
+          market,
+          platformState,
+          user: authority,
+          userTokenAccount: platform.userToken,
+          marketEscrow: escrowFor(market),
+          tokenMint: platform.mint,
+          collateralConfig: collateralConfigFor(platform.mint),
+          treasuryVault: platform.treasuryVault,
+          feeLedger: platform.feeLedger,
+          prediction: predictionFor(market, 0),
+          userStats: userStatsFor(authority, platform.mint),
+          pmpTokenAccount: null,
+          referral: referralFor(authority, platform.mint),
+          referrerRewards: null,
+          burnReceipt: burnReceiptFor(market, authority, nonce),
+          tokenProgram: platform.tokenProgram,
+        },
+        poolState: pool.publicKey,
+        inputMint,
+        userInputAccount,
+        poolTokenAccount,
+        poolUsdcAccount,
+        inputTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    platform = await setupPlatform();
+    market = await createMarket(platform, "swap-bet-1");
+    usdcMint = await createMint(provider.connection, payer, authority, null, 6);
+    userUsdc = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, usdcMint, authority)
+    ).address;
+    await mintTo(provider.connection, payer, usdcMint, userUsdc, payer, 1_000_000_000);
+
+    await program.methods
+      .initializeLiquidityPool("swap-bets", 30)
+      .accountsPartial({
+        poolState: pool.publicKey,
+        platformState,
+        authority,
+        tokenMint: platform.mint,
+        usdcMint,
+        poolTokenAccount,
+        poolUsdcAccount,
+        lpMint,
+      })
+      .signers([pool])
+      .rpc();
+    const userLp = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, lpMint, authority)
+    ).address;
+    await program.methods
+      .addLiquidity(new BN(100_000_000), new BN(100_000_000), new BN(0))
+      .accountsPartial({
+        poolState: pool.publicKey,
+        user: authority,
+        userTokenAccount: platform.userToken,
+        userUsdcAccount: userUsdc,
+        userLpAccount: userLp,
+        poolTokenAccount,
+        poolUsdcAccount,
+        lpMint,
+      })
+      .rpc();
+  });
+
+  it("bets exactly the collateral the pool pays out", async () => {
+    const usdcBefore = await balance(userUsdc);
+    const tokensBefore = await balance(platform.userToken);
+
+    const events = await eventsIn(await swapBet(2_000_000, 1_900_000));
+    const swap = events.find((event) => event.name.toLowerCase() === "swapbetplaced").data as any;
+    const placed = events.find((event) => event.name.toLowerCase() === "predictionplaced").data as any;
+
+    // 100 x 100 tokens of reserves and a 0.3% fee on the 2 USDC in
+    assert.equal(swap.amountOut.toNumber(), 1_955_016);
+    assert.equal(swap.betAmount.toNumber(), 1_955_016);
+    assert.ok(swap.pool.equals(pool.publicKey));
+    assert.ok(swap.inputMint.equals(usdcMint));
+    assert.equal(placed.amount.toNumber(), 1_955_016);
+
+    assert.equal(usdcBefore - (await balance(userUsdc)), 2_000_000);
+    assert.equal(await balance(platform.userToken), tokensBefore);
+    assert.equal(await balance(poolUsdcAccount), 102_000_000);
+    assert.equal(await balance(poolTokenAccount), 100_000_000 - 1_955_016);
+
+    const state = await program.account.liquidityPoolState.fetch(pool.publicKey);
+    assert.equal(state.usdcReserve.toNumber(), 102_000_000);
+    assert.equal(state.tokenReserve.toNumber(), 100_000_000 - 1_955_016);
+  });
+
+  it("fails the whole bet when the output is below the minimum", async () => {
+    const usdcBefore = await balance(userUsdc);
+    await expectError(swapBet(2_000_000, 2_000_000), "SlippageTooHigh");
+    assert.equal(await balance(userUsdc), usdcBefore);
+
+    // Reserves stay booked from what the vaults hold
+    const state = await program.account.liquidityPoolState.fetch(pool.publicKey);
+    assert.equal(state.usdcReserve.toNumber(), await balance(poolUsdcAccount));
+    assert.equal(state.tokenReserve.toNumber(), await balance(poolTokenAccount));
+  });
+
+  it("only swaps through a pool pairing the input mint with the collateral", async () => {
+    const otherMint = await createMint(provider.connection, payer, authority, null, 6);
+    const otherAccount = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, otherMint, authority)
+    ).address;
+    await mintTo(provider.connection, payer, otherMint, otherAccount, payer, 10_000_000);
+
+    await expectError(
+      swapBet(2_000_000, 0, { inputMint: otherMint, userInputAccount: otherAccount }),
+      "InvalidMint"
+    );
+  });
+});
@@ -0,0 +1,51 @@
+import * as anchor from "@coral-xyz/anchor";
+import { getAccount, TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  expectError,
+  escrowFor,
+  addCollateral,
+  setCollateralConfig,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Token-2022 collateral with a transfer fee: bets are credited with what
+// actually reaches the escrow, and a mint only takes bets while enabled.
+describe("token-2022 collateral", () => {
+  let feeMint: Platform;
+
+  const escrowBalance = async (market: anchor.web3.PublicKey) =>
+    (await getAccount(provider.connection, escrowFor(market), undefined, TOKEN_2022_PROGRAM_ID)).amount;
+
+  before(async () => {
+    // One percent of every transfer is withheld by the mint
+    feeMint = await addCollateral(6, 100);
+  });
+
+  it("credits the pool with the amount net of the transfer fee", async () => {
+    const market = await createMarket(feeMint, "token-2022-1");
+    const before = await program.account.market.fetch(market);
+    const escrowBefore = await escrowBalance(market);
+
+    await placePrediction(feeMint, market, 0, 10_000_000);
+
+    const after = await program.account.market.fetch(market);
+    const received = Number((await escrowBalance(market)) - escrowBefore);
+    const credited = after.option1Pool.sub(before.option1Pool).toNumber();
+    const platformFee = after.feesCollected.sub(before.feesCollected).toNumber();
+    assert.equal(credited, received);
+    assert.equal(after.totalPool.sub(before.totalPool).toNumber(), received);
+    assert.isBelow(credited, 10_000_000 - platformFee);
+  });
+
+  it("rejects markets on a disabled Token-2022 mint", async () => {
+    const disabled = await addCollateral(6, 100);
+    await setCollateralConfig(disabled.mint, { enabled: false });
+
+    await expectError(createMarket(disabled, "token-2022-2"), "CollateralNotEnabled");
+  });
+});
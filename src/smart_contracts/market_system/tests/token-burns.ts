@@ -0,0 +1,66 @@
+import * as anchor from "@coral-xyz/anchor";
+import { createMint, getMint } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  expectError,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  claimReward,
+  resolveMarket,
+  Platform,
+} from "./fixtures";
+
+// Burns go through the SPL burn instruction, so every burned share comes off
+// the mint's supply instead of piling up in a burn wallet.
+describe("token burns", () => {
+  let platform: Platform;
+
+  const supply = async () => (await getMint(provider.connection, platform.mint)).supply;
+
+  // Measures how much supply `action` burned
+  const burned = async (action: () => Promise<unknown>) => {
+    const before = await supply();
+    await action();
+    return Number(before - (await supply()));
+  };
+
+  before(async () => {
+    platform = await setupPlatform();
+  });
+
+  it("burns 0.5% of the creator stake", async () => {
+    // The fixture stakes ten tokens
+    assert.equal(await burned(() => createMarket(platform, "burn-1")), 50_000);
+  });
+
+  it("burns the bet burn rate of each prediction", async () => {
+    const market = await createMarket(platform, "burn-2");
+    // The platform is initialized with a 2.5% bet burn rate
+    assert.equal(await burned(() => placePrediction(platform, market, 0, 4_000_000)), 100_000);
+  });
+
+  it("burns the claim burn rate of each reward", async () => {
+    const market = await createMarket(platform, "burn-3");
+    await placePrediction(platform, market, 1);
+    await resolveMarket(market, 1);
+
+    const { totalPool } = await program.account.market.fetch(market);
+    const expected = Math.floor((totalPool.toNumber() * 150) / 10_000);
+    assert.equal(await burned(() => claimReward(platform, market, 1)), expected);
+  });
+
+  it("only burns from the market's own mint", async () => {
+    const market = await createMarket(platform, "burn-4");
+    const otherMint = await createMint(provider.connection, payer, authority, null, 6);
+
+    await expectError(
+      placePrediction({ ...platform, mint: otherMint }, market, 0),
+      "InvalidMint"
+    );
+  });
+});
@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getAccount, getOrCreateAssociatedTokenAccount, transfer } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  treasury,
+  expectError,
+  escrowFor,
+  predictionFor,
+  referralFor,
+  burnReceiptFor,
+  nextBurnNonce,
+  fundedUser,
+  setupPlatform,
+  createMarket,
+  placePrediction,
+  Platform,
+} from "./fixtures";
+
+// Bet fees accrue in the mint's treasury vault PDA and only leave it through
+// withdraw_treasury, to an account owned by PlatformState.treasury.
+describe("treasury", () => {
+  let platform: Platform;
+  let market: anchor.web3.PublicKey;
+  let treasuryToken: anchor.web3.PublicKey;
+
+  const withdraw = (
+    amount: BN,
+    destination = treasuryToken,
+    signer?: anchor.web3.Keypair
+  ) =>
+    program.methods
+      .withdrawTreasury(amount)
+      .accountsPartial({
+        platformState,
+        authority: signer?.publicKey ?? authority,
+        treasuryVault: platform.treasuryVault,
+        feeLedger: platform.feeLedger,
+        treasuryTokenAccount: destination,
+        tokenMint: platform.mint,
+        tokenProgram: platform.tokenProgram,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  before(async () => {
+    platform = await setupPlatform();
+    market = await createMarket(platform, "treasury-1");
+    treasuryToken = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, platform.mint, treasury)
+    ).address;
+    await placePrediction(platform, market, 0, 20_000_000);
+  });
+
+  it("only takes bet fees into the treasury vault PDA", async () => {
+    const nonce = nextBurnNonce();
+    await expectError(
+      program.methods
+        .placePrediction(0, new BN(5_000_000), nonce)
+        .accountsPartial({
+          market,
+          platformState,
+          user: authority,
+          userTokenAccount: platform.userToken,
+          marketEscrow: escrowFor(market),
+          tokenMint: platform.mint,
+          treasuryVault: platform.userToken,
+          feeLedger: platform.feeLedger,
+          prediction: predictionFor(market, 0),
+          pmpTokenAccount: null,
+          tokenProgram: platform.tokenProgram,
+          referral: referralFor(authority, platform.mint),
+          referrerRewards: null,
+          burnReceipt: burnReceiptFor(market, authority, nonce),
+        })
+        .rpc(),
+      "ConstraintSeeds"
+    );
+  });
+
+  it("pays accrued fees out to the configured treasury", async () => {
+    const ledger = await program.account.feeLedger.fetch(platform.feeLedger);
+    const amount = ledger.treasuryAccrued.divn(2);
+    assert.isTrue(amount.gtn(0));
+
+    await withdraw(amount);
+
+    const after = await program.account.feeLedger.fetch(platform.feeLedger);
+    assert.equal(ledger.treasuryAccrued.sub(after.treasuryAccrued).toString(), amount.toString());
+    const balance = (await getAccount(provider.connection, treasuryToken)).amount;
+    assert.equal(balance.toString(), amount.toString());
+  });
+
+  it("rejects other destinations, other signers and more than accrued", async () => {
+    await expectError(withdraw(new BN(1), platform.userToken), "Unauthorized");
+
+    const stranger = await fundedUser(platform);
+    await expectError(withdraw(new BN(1), treasuryToken, stranger.user), "Unauthorized");
+
+    // Tokens sent straight to the vault are held but were never accrued
+    await transfer(provider.connection, payer, platform.userToken, platform.treasuryVault, payer, 1_000_000);
+    const ledger = await program.account.feeLedger.fetch(platform.feeLedger);
+    await expectError(withdraw(ledger.treasuryAccrued.addn(1)), "InsufficientAccruedFees");
+  });
+});
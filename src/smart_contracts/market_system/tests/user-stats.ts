@@ -0,0 +1,81 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program,
+  provider,
+  payer,
+  authority,
+  platformState,
+  userStatsFor,
+  fundedUser,
+  setupPlatform,
+  addCollateral,
+  createMarket,
+  placePrediction,
+  Bettor,
+  Platform,
+} from "./fixtures";
+
+// Volume is tracked per collateral mint, and fee tiers are set in whole
+// tokens so the same thresholds hold for mints of any decimals.
+describe("per-mint user stats", () => {
+  let sixDecimals: Platform;
+  let nineDecimals: Platform;
+  let bettor: Bettor;
+  let bettorNine: Bettor;
+
+  const setFeeTiers = (tiers: object[]) =>
+    program.methods.setFeeTiers(tiers).accountsPartial({ platformState, authority }).rpc();
+
+  const feesOn = async (market: anchor.web3.PublicKey) =>
+    (await program.account.market.fetch(market)).feesCollected.toNumber();
+
+  before(async () => {
+    sixDecimals = await setupPlatform();
+    nineDecimals = await addCollateral(9);
+    bettor = await fundedUser(sixDecimals);
+    const token = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection, payer, nineDecimals.mint, bettor.user.publicKey
+      )
+    ).address;
+    await mintTo(provider.connection, payer, nineDecimals.mint, token, payer, 1_000_000_000_000);
+    bettorNine = { user: bettor.user, token };
+    await setFeeTiers([{ minVolume: new BN(10), platformFeeRate: new BN(0), betBurnRate: new BN(0) }]);
+  });
+
+  after(async () => {
+    await setFeeTiers([]);
+  });
+
+  it("keeps one stats account per mint in that mint's units", async () => {
+    const marketSix = await createMarket(sixDecimals, "stats-six");
+    const marketNine = await createMarket(nineDecimals, "stats-nine");
+    await placePrediction(sixDecimals, marketSix, 0, 2_000_000, { bettor });
+    await placePrediction(nineDecimals, marketNine, 0, 2_000_000_000, { bettor: bettorNine });
+
+    const six = await program.account.userStats.fetch(userStatsFor(bettor.user.publicKey, sixDecimals.mint));
+    const nine = await program.account.userStats.fetch(userStatsFor(bettor.user.publicKey, nineDecimals.mint));
+    assert.ok(six.mint.equals(sixDecimals.mint));
+    assert.ok(nine.mint.equals(nineDecimals.mint));
+    assert.equal(six.totalVolume.toNumber(), 2_000_000);
+    assert.equal(nine.totalVolume.toNumber(), 2_000_000_000);
+  });
+
+  it("applies a whole-token tier only where the volume was reached", async () => {
+    const marketSix = await createMarket(sixDecimals, "tier-six");
+    const marketNine = await createMarket(nineDecimals, "tier-nine");
+
+    // Ten whole tokens in the six-decimal mint reaches the tier there
+    await placePrediction(sixDecimals, marketSix, 0, 10_000_000, { bettor });
+    const before = await feesOn(marketSix);
+    await placePrediction(sixDecimals, marketSix, 0, 1_000_000, { bettor });
+    assert.equal(await feesOn(marketSix), before);
+
+    // The same user is still below the tier in the nine-decimal mint
+    await placePrediction(nineDecimals, marketNine, 0, 1_000_000_000, { bettor: bettorNine });
+    assert.isAbove(await feesOn(marketNine), 0);
+  });
+});